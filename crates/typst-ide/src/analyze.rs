@@ -46,6 +46,8 @@ pub fn analyze_expr(world: &dyn World, node: &LinkedNode) -> EcoVec<Value> {
 
 /// Try to load a module from the current source file.
 pub fn analyze_import(world: &dyn World, source: &LinkedNode) -> Option<Value> {
+    // Relative paths are resolved with respect to the source's file.
+    let span = source.span();
     let source = analyze_expr(world, source).into_iter().next()?;
     if source.scope().is_some() {
         return Some(source);
@@ -63,7 +65,7 @@ pub fn analyze_import(world: &dyn World, source: &LinkedNode) -> Option<Value> {
     };
 
     let mut vm = Vm::new(engine, Scopes::new(Some(world.library())), Span::detached());
-    typst::eval::import(&mut vm, source, Span::detached(), true)
+    typst::eval::import(&mut vm, source, span, true)
         .ok()
         .map(Value::Module)
 }
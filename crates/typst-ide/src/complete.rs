@@ -1,33 +1,57 @@
 use std::cmp::Reverse;
 use std::collections::{BTreeSet, HashSet};
+use std::ffi::OsStr;
+use std::fmt::Write;
+use std::path::Path;
+use std::sync::Arc;
 
+use comemo::Prehashed;
 use ecow::{eco_format, EcoString};
 use if_chain::if_chain;
 use serde::{Deserialize, Serialize};
 use typst::foundations::{
-    fields_on, format_str, mutable_methods_on, repr, AutoValue, CastInfo, Func, Label,
-    NoneValue, Repr, Scope, Type, Value,
+    fields_on, format_str, mutable_methods_on, repr, Array, AutoValue, CastInfo, Content,
+    Dict, Element, Func, IntoValue, Label, NoneValue, ParamInfo, Repr, Scope, Str, Type,
+    Value,
 };
-use typst::model::Document;
+use typst::layout::{
+    Abs, Angle, Em, Fr, HAlignment, Length, Paper, Ratio, Rel, VAlignment,
+};
+use typst::model::{BibliographyElem, Document};
 use typst::syntax::{
-    ast, is_id_continue, is_id_start, is_ident, LinkedNode, Source, SyntaxKind,
+    ast::{self, AstNode},
+    is_id_continue, is_id_start, is_ident, FileId, LinkedNode, Source, SyntaxKind,
 };
-use typst::text::RawElem;
+use typst::text::{FontWeight, Lang, RawElem, Region};
 use typst::visualize::Color;
-use typst::World;
+use typst::{Library, World};
 use unscanny::Scanner;
 
 use crate::analyze::{analyze_expr, analyze_import, analyze_labels};
-use crate::{plain_docs_sentence, summarize_font_family};
+use crate::definition::definition;
+use crate::signature::compact_signature;
+use crate::{fuzzy_match, plain_docs_sentence, summarize_font_family};
 
 /// Autocomplete a cursor position in a source file.
 ///
-/// Returns the position from which the completions apply and a list of
-/// completions.
+/// Returns the range that the completions replace and a list of completions.
+/// The range covers the whole identifier at the cursor, not only its part
+/// before the cursor: "#pa|ge".
 ///
 /// When `explicit` is `true`, the user requested the completion by pressing
 /// control and space or something similar.
 ///
+/// The `trigger` is the character whose typing requested the completion, if
+/// any. Some characters change which completions are offered:
+/// - After a `.`, only fields are completed, even if none are found for the
+///   target.
+/// - After a space, only set and show rules and argument lists are completed:
+///   "set |", "text(fill: |)".
+///
+/// With a `max`, at most that many of the best ranked completions are
+/// returned. The trailing `bool` is `true` if some were cut off, so that the
+/// completions should be requested again as the user types.
+///
 /// Passing a `document` (from a previous compilation) is optional, but enhances
 /// the autocompletions. Label completions, for instance, are only generated
 /// when the document is available.
@@ -37,20 +61,64 @@ pub fn autocomplete(
     source: &Source,
     cursor: usize,
     explicit: bool,
-) -> Option<(usize, Vec<Completion>)> {
-    let mut ctx = CompletionContext::new(world, document, source, cursor, explicit)?;
+    trigger: Option<char>,
+    max: Option<usize>,
+) -> Option<(usize, usize, Vec<Completion>, bool)> {
+    let mut ctx =
+        CompletionContext::new(world, document, source, cursor, explicit, trigger)?;
+
+    // Comments and raw text aren't Typst, so only a raw block's language tag
+    // gets completions: "```ru|".
+    if matches!(ctx.leaf.kind(), SyntaxKind::LineComment | SyntaxKind::BlockComment) {
+        return None;
+    } else if ctx.leaf.text().starts_with('`') {
+        if !complete_raw_lang(&mut ctx) {
+            return None;
+        }
+    } else if ctx.trigger == Some(' ') {
+        let _ = complete_rules(&mut ctx) || complete_params(&mut ctx);
+    } else {
+        let _ = complete_set_targets(&mut ctx)
+            || complete_field_accesses(&mut ctx)
+            || complete_open_labels(&mut ctx)
+            || complete_imports(&mut ctx)
+            || complete_file_paths(&mut ctx)
+            || complete_string_escapes(&mut ctx)
+            || complete_rules(&mut ctx)
+            || complete_destructuring(&mut ctx)
+            || complete_param_arrays(&mut ctx)
+            || complete_params(&mut ctx)
+            || complete_collection(&mut ctx)
+            || complete_markup(&mut ctx)
+            || complete_math(&mut ctx)
+            || complete_code(&mut ctx);
+    }
+
+    rank_completions(&mut ctx);
+
+    let incomplete = max.is_some_and(|max| ctx.completions.len() > max);
+    if let Some(max) = max {
+        ctx.completions.truncate(max);
+    }
 
-    let _ = complete_comments(&mut ctx)
-        || complete_field_accesses(&mut ctx)
-        || complete_open_labels(&mut ctx)
-        || complete_imports(&mut ctx)
-        || complete_rules(&mut ctx)
-        || complete_params(&mut ctx)
-        || complete_markup(&mut ctx)
-        || complete_math(&mut ctx)
-        || complete_code(&mut ctx);
+    Some((ctx.from, ctx.to, ctx.completions, incomplete))
+}
+
+/// Rank completions whose label starts with the typed text above the others:
+/// "#pa|" favors "page" and "par". Completions with the same score are sorted
+/// by label.
+fn rank_completions(ctx: &mut CompletionContext) {
+    let typed = ctx.text.get(ctx.from..ctx.cursor).unwrap_or_default();
+    if !typed.is_empty() {
+        for completion in &mut ctx.completions {
+            if completion.label.starts_with(typed) {
+                completion.score += 1.0;
+            }
+        }
+    }
 
-    Some((ctx.from, ctx.completions))
+    ctx.completions
+        .sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.label.cmp(&b.label)));
 }
 
 /// An autocompletion option.
@@ -61,12 +129,29 @@ pub struct Completion {
     /// The label the completion is shown with.
     pub label: EcoString,
     /// The completed version of the input, possibly described with snippet
-    /// syntax like `${lhs} + ${rhs}`.
+    /// syntax like `${lhs} + ${rhs}`. Snippets of syntax have numbered
+    /// placeholders like `${1:lhs} + ${2:rhs}`.
     ///
     /// Should default to the `label` if `None`.
     pub apply: Option<EcoString>,
-    /// An optional short description, at most one sentence.
+    /// An optional short description, at most one sentence. For functions,
+    /// it is preceded by their signature on a line of its own.
     pub detail: Option<EcoString>,
+    /// The complete documentation of the completed item as Markdown, for an
+    /// expanded view of the completion.
+    pub documentation: Option<EcoString>,
+    /// How relevant the completion is at the cursor. Higher is better.
+    ///
+    /// Completions are returned sorted by it, with ties kept in the order in
+    /// which they are produced.
+    pub score: f32,
+    /// The indices of the label's characters that match the typed text, so
+    /// that they can be highlighted.
+    pub matched: Vec<usize>,
+    /// Further replacements in the source that go along with the completion,
+    /// as start and end offsets with the replacement text. Used to import what
+    /// is completed.
+    pub additional_edits: Vec<(usize, usize, EcoString)>,
 }
 
 /// A kind of item that can be completed.
@@ -85,20 +170,214 @@ pub enum CompletionKind {
     Constant,
     /// A symbol.
     Symbol(char),
+    /// A label.
+    Label,
+    /// A color, with its 8-bit RGBA components packed into an integer, so
+    /// that a swatch can be shown.
+    Color(u32),
+}
+
+/// How the text of a completion is inserted, as in the Language Server
+/// Protocol's `InsertTextFormat`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum InsertTextFormat {
+    /// The text is inserted as is.
+    PlainText,
+    /// The text is a snippet with tab stops and placeholders.
+    Snippet,
+}
+
+/// Translate the snippet syntax of a completion's `apply` into the one of the
+/// Language Server Protocol: "${lhs} + ${rhs}" becomes "${1:lhs} + ${2:rhs}".
+///
+/// Placeholders are numbered like by [`normalize_snippet`]. Literal `$`, `}`,
+/// and `\` are escaped. Text without placeholders is returned unchanged as
+/// plain text.
+pub fn to_lsp_snippet(apply: &str) -> (EcoString, InsertTextFormat) {
+    if !apply.contains("${") {
+        return (apply.into(), InsertTextFormat::PlainText);
+    }
+
+    (number_placeholders(apply, true), InsertTextFormat::Snippet)
+}
+
+/// Bring a snippet into the canonical form in which every placeholder has a
+/// number: "${lhs} + ${}" becomes "${1:lhs} + ${2}".
+///
+/// Placeholders are numbered in order of appearance, skipping the numbers of
+/// those that already have one, like `${2:default}` or `${2}`. Placeholders
+/// may be nested.
+fn normalize_snippet(snippet: &str) -> EcoString {
+    number_placeholders(snippet, false)
+}
+
+/// Number the placeholders of a snippet, optionally escaping literal text.
+fn number_placeholders(snippet: &str, escape: bool) -> EcoString {
+    let numbered: Vec<usize> = snippet
+        .match_indices("${")
+        .filter_map(|(i, _)| {
+            let rest = &snippet[i + 2..];
+            let digits =
+                rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            if digits > 0 && rest[digits..].starts_with([':', '}']) {
+                rest[..digits].parse().ok()
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    let mut output = EcoString::new();
+    let mut next = 1;
+    let mut s = Scanner::new(snippet);
+    write_placeholders(&mut s, &mut output, &numbered, &mut next, escape, false);
+    output
+}
+
+/// Write snippet text with numbered placeholders up to the end of the current
+/// placeholder, if `nested`, or of the text.
+fn write_placeholders(
+    s: &mut Scanner,
+    output: &mut EcoString,
+    numbered: &[usize],
+    next: &mut usize,
+    escape: bool,
+    nested: bool,
+) {
+    while let Some(c) = s.eat() {
+        match c {
+            '$' if s.eat_if('{') => {
+                let start = s.cursor();
+                let digits = s.eat_while(|c: char| c.is_ascii_digit());
+                let index = match digits.parse() {
+                    Ok(index) if s.eat_if(':') || s.at('}') => index,
+                    _ => {
+                        s.jump(start);
+                        while numbered.contains(next) {
+                            *next += 1;
+                        }
+                        *next += 1;
+                        *next - 1
+                    }
+                };
+
+                if s.eat_if('}') {
+                    write!(output, "${{{index}}}").unwrap();
+                } else {
+                    write!(output, "${{{index}:").unwrap();
+                    write_placeholders(s, output, numbered, next, escape, true);
+                    output.push('}');
+                }
+            }
+            '}' if nested => return,
+            '$' | '}' | '\\' if escape => {
+                output.push('\\');
+                output.push(c);
+            }
+            c => output.push(c),
+        }
+    }
+}
+
+/// Find all labels in the syntax tree, along with the kind of element each
+/// one is attached to.
+fn source_labels(node: &LinkedNode, labels: &mut Vec<(EcoString, Option<EcoString>)>) {
+    if let Some(label) = node.cast::<ast::Label>() {
+        let kind = node.prev_sibling().and_then(|prev| match prev.kind() {
+            SyntaxKind::Heading => Some("heading"),
+            SyntaxKind::Equation => Some("equation"),
+            SyntaxKind::FuncCall => match prev.cast::<ast::FuncCall>()?.callee() {
+                ast::Expr::Ident(callee) if callee.as_str() == "figure" => Some("figure"),
+                _ => None,
+            },
+            _ => None,
+        });
+        labels.push((label.get().into(), kind.map(Into::into)));
+    }
+
+    for child in node.children() {
+        source_labels(&child, labels);
+    }
+}
+
+/// Find the keys of all bibliographies in the syntax tree, along with the
+/// titles of their entries: "#bibliography("works.bib")".
+fn source_bibliography_keys(
+    world: &dyn World,
+    node: &LinkedNode,
+    keys: &mut Vec<(EcoString, Option<EcoString>)>,
+) {
+    if_chain! {
+        if let Some(call) = node.cast::<ast::FuncCall>();
+        if let ast::Expr::Ident(callee) = call.callee();
+        if callee.as_str() == "bibliography";
+        if let Some(ast::Arg::Pos(arg)) = call.args().items().next();
+        if let Some(id) = node.span().id();
+        then {
+            let paths: Vec<EcoString> = match arg {
+                ast::Expr::Str(path) => vec![path.get()],
+                ast::Expr::Array(array) => array
+                    .items()
+                    .filter_map(|item| match item {
+                        ast::ArrayItem::Pos(ast::Expr::Str(path)) => Some(path.get()),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => return,
+            };
+
+            let Ok(data) = paths
+                .iter()
+                .map(|path| world.file(id.join(path)))
+                .collect::<Result<Vec<_>, _>>()
+            else {
+                return;
+            };
+
+            keys.extend(BibliographyElem::load_keys(paths, &data).unwrap_or_default());
+            return;
+        }
+    }
+
+    for child in node.children() {
+        source_bibliography_keys(world, &child, keys);
+    }
 }
 
-/// Complete in comments. Or rather, don't!
-fn complete_comments(ctx: &mut CompletionContext) -> bool {
-    matches!(ctx.leaf.kind(), SyntaxKind::LineComment | SyntaxKind::BlockComment)
+/// Complete the language tag of a raw block, possibly an unclosed one. Only
+/// the tag is replaced, not the backticks: "```py|".
+fn complete_raw_lang(ctx: &mut CompletionContext) -> bool {
+    let mut s = Scanner::new(ctx.text);
+    s.jump(ctx.leaf.offset());
+    if !s.eat_if("```") {
+        return false;
+    }
+
+    s.eat_while('`');
+    let start = s.cursor();
+    if s.eat_if(is_id_start) {
+        s.eat_while(is_id_continue);
+    }
+
+    if s.cursor() != ctx.cursor {
+        return false;
+    }
+
+    ctx.from = start;
+    ctx.raw_completions();
+    true
 }
 
 /// Complete in markup mode.
 fn complete_markup(ctx: &mut CompletionContext) -> bool {
-    // Bail if we aren't even in markup.
+    // Bail if we aren't even in markup (or directly at its start: "[|]"),
+    // possibly of an unclosed content block: "[|".
     if !matches!(
         ctx.leaf.parent_kind(),
         None | Some(SyntaxKind::Markup) | Some(SyntaxKind::Ref)
-    ) {
+    ) && ctx.leaf.kind() != SyntaxKind::LeftBracket
+        && !is_unclosed_bracket(&ctx.leaf)
+    {
         return false;
     }
 
@@ -112,6 +391,7 @@ fn complete_markup(ctx: &mut CompletionContext) -> bool {
     // An existing identifier: "#pa|".
     if ctx.leaf.kind() == SyntaxKind::Ident {
         ctx.from = ctx.leaf.offset();
+        ctx.to = ctx.leaf.range().end;
         code_completions(ctx, true);
         return true;
     }
@@ -119,6 +399,7 @@ fn complete_markup(ctx: &mut CompletionContext) -> bool {
     // Start of a reference: "@|" or "@he|".
     if ctx.leaf.kind() == SyntaxKind::RefMarker {
         ctx.from = ctx.leaf.offset() + 1;
+        ctx.to = ctx.leaf.range().end;
         ctx.label_completions();
         return true;
     }
@@ -135,22 +416,6 @@ fn complete_markup(ctx: &mut CompletionContext) -> bool {
         }
     }
 
-    // Directly after a raw block.
-    let mut s = Scanner::new(ctx.text);
-    s.jump(ctx.leaf.offset());
-    if s.eat_if("```") {
-        s.eat_while('`');
-        let start = s.cursor();
-        if s.eat_if(is_id_start) {
-            s.eat_while(is_id_continue);
-        }
-        if s.cursor() == ctx.cursor {
-            ctx.from = start;
-            ctx.raw_completions();
-        }
-        return true;
-    }
-
     // Anywhere: "|".
     if ctx.explicit {
         ctx.from = ctx.cursor;
@@ -164,6 +429,50 @@ fn complete_markup(ctx: &mut CompletionContext) -> bool {
 /// Add completions for markup snippets.
 #[rustfmt::skip]
 fn markup_completions(ctx: &mut CompletionContext) {
+    // Content blocks are easily left unclosed: "[...|".
+    if in_unclosed_content_block(ctx) {
+        ctx.snippet_completion(
+            "]",
+            "]",
+            "Closes the content block.",
+        );
+    }
+
+    // Pages are mostly set up before any content: "|".
+    if at_document_start(ctx) {
+        ctx.snippet_completion(
+            "page setup",
+            "#set page(paper: \"${a4}\", margin: ${2cm}, numbering: \"${1}\")",
+            "Sets up the paper size, margins, and page numbering.",
+        );
+    }
+
+    // Footnotes and captions mostly hold references and citations.
+    let caption = in_caption(ctx);
+    let referencing = caption || in_content_arg_of(ctx, "footnote");
+    if referencing {
+        ctx.snippet_completion(
+            "reference",
+            "@${label}",
+            "Inserts a reference to a label.",
+        );
+
+        ctx.snippet_completion(
+            "citation",
+            "#cite(<${key}>)",
+            "Cites an entry from the bibliography.",
+        );
+    }
+
+    // Captions often name works in italics.
+    if caption {
+        ctx.snippet_completion(
+            "italic text",
+            "#text(style: \"italic\")[${}]",
+            "Sets content in italic font style.",
+        );
+    }
+
     ctx.snippet_completion(
         "expression",
         "#${}",
@@ -212,11 +521,13 @@ fn markup_completions(ctx: &mut CompletionContext) {
         "Makes the preceding element referenceable.",
     );
 
-    ctx.snippet_completion(
-        "reference",
-        "@${name}",
-        "Inserts a reference to a label.",
-    );
+    if !referencing {
+        ctx.snippet_completion(
+            "reference",
+            "@${name}",
+            "Inserts a reference to a label.",
+        );
+    }
 
     ctx.snippet_completion(
         "heading",
@@ -224,6 +535,30 @@ fn markup_completions(ctx: &mut CompletionContext) {
         "Inserts a section heading.",
     );
 
+    ctx.snippet_completion(
+        "heading (labelled)",
+        "= ${title} <${label}>",
+        "Inserts a section heading that can be referenced.",
+    );
+
+    ctx.snippet_completion(
+        "heading (level 2)",
+        "== ${title}",
+        "Inserts a subsection heading.",
+    );
+
+    ctx.snippet_completion(
+        "heading (level 3)",
+        "=== ${title}",
+        "Inserts a heading below a subsection.",
+    );
+
+    ctx.snippet_completion(
+        "heading (level 4)",
+        "==== ${title}",
+        "Inserts a heading four levels deep.",
+    );
+
     ctx.snippet_completion(
         "list item",
         "- ${item}",
@@ -261,6 +596,116 @@ fn markup_completions(ctx: &mut CompletionContext) {
     );
 }
 
+/// Whether the cursor is in a content block passed as a figure's caption:
+/// "#figure(.., caption: [|])".
+fn in_caption(ctx: &CompletionContext) -> bool {
+    if_chain! {
+        if let Some(block) = enclosing_content_block(&ctx.leaf);
+        if let Some(named) = block.parent().and_then(|named| named.cast::<ast::Named>());
+        then {
+            return named.name().as_str() == "caption";
+        }
+    }
+
+    false
+}
+
+/// Find the innermost content block around a node.
+fn enclosing_content_block<'a, 'b>(
+    node: &'b LinkedNode<'a>,
+) -> Option<&'b LinkedNode<'a>> {
+    let mut node = node.parent();
+    while let Some(parent) = node {
+        if parent.kind() == SyntaxKind::ContentBlock {
+            return Some(parent);
+        }
+        node = parent.parent();
+    }
+    None
+}
+
+/// Whether the cursor is in a content block argument of a call to the
+/// function with the given name: "#footnote[|]".
+fn in_content_arg_of(ctx: &CompletionContext, name: &str) -> bool {
+    if_chain! {
+        if let Some(block) = enclosing_content_block(&ctx.leaf);
+        if let Some(args) = block.parent();
+        if args.kind() == SyntaxKind::Args;
+        if let Some(call) = args.parent().and_then(|call| call.cast::<ast::FuncCall>());
+        if let ast::Expr::Ident(callee) = call.callee();
+        then {
+            return callee.as_str() == name;
+        }
+    }
+
+    false
+}
+
+/// Whether only rules and bindings precede the cursor at the top level of the
+/// document, so that it is still being set up.
+fn at_document_start(ctx: &CompletionContext) -> bool {
+    let mut node = ctx.leaf.clone();
+    while let Some(parent) = node.parent() {
+        if parent.parent().is_none() {
+            break;
+        }
+
+        // Markup nested in code isn't the top level: "#{[#|]}".
+        if parent.kind() == SyntaxKind::ContentBlock {
+            return false;
+        }
+
+        node = parent.clone();
+    }
+
+    match node.parent_kind() {
+        Some(SyntaxKind::Markup) => {}
+        None => return true,
+        Some(_) => return false,
+    }
+
+    let mut sibling = node.prev_sibling();
+    while let Some(prev) = sibling {
+        if !matches!(
+            prev.kind(),
+            SyntaxKind::Hash
+                | SyntaxKind::SetRule
+                | SyntaxKind::ShowRule
+                | SyntaxKind::LetBinding
+                | SyntaxKind::ModuleImport
+        ) {
+            return false;
+        }
+        sibling = prev.prev_sibling();
+    }
+
+    true
+}
+
+/// Whether the cursor is in a content block that was never closed: "[...|".
+fn in_unclosed_content_block(ctx: &CompletionContext) -> bool {
+    let mut node = Some(ctx.leaf.clone());
+    while let Some(current) = node {
+        if current.kind() == SyntaxKind::ContentBlock
+            && current
+                .children()
+                .next()
+                .is_some_and(|first| is_unclosed_bracket(&first))
+        {
+            return true;
+        }
+        node = current.parent().cloned();
+    }
+    false
+}
+
+/// Whether the node is the opening bracket of an unclosed content block.
+fn is_unclosed_bracket(node: &LinkedNode) -> bool {
+    node.kind() == SyntaxKind::Error
+        && node.text() == "["
+        && node.parent_kind() == Some(SyntaxKind::ContentBlock)
+}
+
 /// Complete in math mode.
 fn complete_math(ctx: &mut CompletionContext) -> bool {
     if !matches!(
@@ -283,6 +728,7 @@ fn complete_math(ctx: &mut CompletionContext) -> bool {
     // Behind existing atom or identifier: "$a|$" or "$abc|$".
     if matches!(ctx.leaf.kind(), SyntaxKind::Text | SyntaxKind::MathIdent) {
         ctx.from = ctx.leaf.offset();
+        ctx.to = ctx.leaf.range().end;
         math_completions(ctx);
         return true;
     }
@@ -297,11 +743,178 @@ fn complete_math(ctx: &mut CompletionContext) -> bool {
     false
 }
 
+/// Add completions for the shorthands of math mode, along with the symbols
+/// they stand for: "->" for "→".
+fn math_shorthand_completions(ctx: &mut CompletionContext) {
+    // Shorthands consist of punctuation, so they don't match names.
+    if ctx.from < ctx.cursor {
+        return;
+    }
+
+    for &(shorthand, c) in ast::Shorthand::MATH_LIST {
+        // Single characters are quicker typed than picked.
+        if shorthand.chars().count() < 2 {
+            continue;
+        }
+
+        let name = ctx.math.iter().find_map(|(name, value)| {
+            let Value::Symbol(symbol) = value else { return None };
+            let (modifiers, _) = symbol.variants().find(|&(_, glyph)| glyph == c)?;
+            Some(if modifiers.is_empty() {
+                name.clone()
+            } else {
+                eco_format!("{name}.{modifiers}")
+            })
+        });
+
+        ctx.completions.push(Completion {
+            kind: CompletionKind::Symbol(c),
+            label: shorthand.into(),
+            apply: None,
+            detail: Some(match name {
+                Some(name) => eco_format!("Shorthand for {c} (`{name}`)."),
+                None => eco_format!("Shorthand for {c}."),
+            }),
+            documentation: None,
+            score: 0.0,
+            matched: vec![],
+            additional_edits: vec![],
+        });
+    }
+}
+
+/// Which attachment of a math attachment the cursor is in, if any: the
+/// bottom ("$a_|$") or the top ("$a^|$").
+fn attachment_slot(ctx: &CompletionContext) -> Option<SyntaxKind> {
+    let attachment = |kind: SyntaxKind| {
+        matches!(kind, SyntaxKind::Underscore | SyntaxKind::Hat).then_some(kind)
+    };
+
+    if ctx.leaf.parent_kind() == Some(SyntaxKind::MathAttach) {
+        if let Some(kind) = attachment(ctx.leaf.kind()) {
+            return Some(kind);
+        }
+    }
+
+    let mut node = ctx.leaf.clone();
+    while let Some(parent) = node.parent() {
+        match parent.kind() {
+            SyntaxKind::MathAttach => {
+                return node.prev_sibling_kind().and_then(attachment)
+            }
+            SyntaxKind::Equation => break,
+            _ => node = parent.clone(),
+        }
+    }
+
+    None
+}
+
 /// Add completions for math snippets.
 #[rustfmt::skip]
 fn math_completions(ctx: &mut CompletionContext) {
     ctx.scope_completions(true, |_| true);
 
+    // Variants of symbols are found by their full name: "$arr|$" finds
+    // "arrow.r". Only the best few of those starting like the typed text are
+    // offered, as short fragments match lots of them.
+    const MAX_VARIANTS: usize = 50;
+    let typed = ctx.text.get(ctx.from..ctx.cursor).unwrap_or_default();
+    if let Some(first) = typed.chars().next() {
+        let mut variants = vec![];
+        for (name, value) in ctx.math.iter() {
+            let Value::Symbol(symbol) = value else { continue };
+            if !name.starts_with(first) {
+                continue;
+            }
+
+            for (modifiers, c) in symbol.variants() {
+                if modifiers.is_empty() {
+                    continue;
+                }
+
+                let label = eco_format!("{name}.{modifiers}");
+                let Some(m) = fuzzy_match(typed, &label) else { continue };
+                variants.push((Reverse(m.score), label, c, m.indices));
+            }
+        }
+
+        variants.sort();
+        for (_, label, c, matched) in variants.into_iter().take(MAX_VARIANTS) {
+            ctx.completions.push(Completion {
+                kind: CompletionKind::Symbol(c),
+                label,
+                apply: None,
+                detail: None,
+                documentation: None,
+                score: 0.0,
+                matched,
+                additional_edits: vec![],
+            });
+        }
+    }
+
+    // Shorthands are quicker to type than names: "->" for "arrow.r".
+    for completion in &mut ctx.completions {
+        let CompletionKind::Symbol(c) = completion.kind else { continue };
+        if let Some((shorthand, _)) =
+            ast::Shorthand::MATH_LIST.iter().find(|&&(_, glyph)| glyph == c)
+        {
+            let note = eco_format!("Shorthand: `{shorthand}`.");
+            completion.detail = Some(match &completion.detail {
+                Some(detail) => eco_format!("{detail} {note}"),
+                None => note,
+            });
+        }
+    }
+
+    // Spell out the arguments of functions whose structure isn't obvious.
+    for completion in &mut ctx.completions {
+        if !matches!(completion.kind, CompletionKind::Func) {
+            continue;
+        }
+
+        if let Some(&(_, snippet)) =
+            MATH_FUNC_SNIPPETS.iter().find(|&&(name, _)| completion.label == name)
+        {
+            completion.apply = Some(normalize_snippet(snippet));
+        }
+    }
+
+    // Radicals are common, but their argument order is easily forgotten.
+    for completion in &mut ctx.completions {
+        if matches!(completion.kind, CompletionKind::Func)
+            && matches!(completion.label.as_str(), "sqrt" | "root")
+        {
+            completion.score += 0.25;
+        }
+    }
+
+    // Attachments mostly hold variables and bounds: "$integral_|$".
+    let slot = attachment_slot(ctx);
+    if slot.is_some() {
+        let locals = ctx.local_bindings();
+        for completion in &mut ctx.completions {
+            completion.score += if locals.contains(&completion.label) {
+                1.0
+            } else if matches!(completion.label.as_str(), "oo" | "infinity") {
+                0.5
+            } else {
+                0.0
+            };
+        }
+    }
+
+    if slot == Some(SyntaxKind::Underscore) {
+        ctx.snippet_completion(
+            "index range",
+            "(${i}=${0})",
+            "Sets an index and where it starts.",
+        );
+    }
+
+    math_shorthand_completions(ctx);
+
     ctx.snippet_completion(
         "subscript",
         "${x}_${2:2}",
@@ -319,8 +932,58 @@ fn math_completions(ctx: &mut CompletionContext) {
         "${x}/${y}",
         "Inserts a fraction.",
     );
+
+    // Accents are symbols that are called with their base.
+    ctx.snippet_completion(
+        "hat accent",
+        "hat(${x})",
+        "Puts a circumflex over something.",
+    );
+
+    ctx.snippet_completion(
+        "tilde accent",
+        "tilde(${x})",
+        "Puts a tilde over something.",
+    );
+
+    ctx.snippet_completion(
+        "macron accent",
+        "macron(${x})",
+        "Puts a short bar over something.",
+    );
+
+    ctx.snippet_completion(
+        "arrow accent",
+        "arrow(${x})",
+        "Puts an arrow over something, like a vector.",
+    );
+
+    ctx.snippet_completion(
+        "dot accent",
+        "dot(${x})",
+        "Puts a dot over something, like a time derivative.",
+    );
+
+    ctx.snippet_completion(
+        "double dot accent",
+        "dot.double(${x})",
+        "Puts two dots over something, like a second time derivative.",
+    );
 }
 
+/// Applications for math functions, with placeholders for their arguments.
+const MATH_FUNC_SNIPPETS: &[(&str, &str)] = &[
+    ("vec", "vec(${1}, ${2})"),
+    ("mat", "mat(${1}, ${2}; ${3}, ${4})"),
+    ("cases", "cases(${1} \"if\" ${x}, ${0} \"else\")"),
+    ("sqrt", "sqrt(${x})"),
+    ("root", "root(${index}, ${radicand})"),
+    ("cancel", "cancel(${x})"),
+    ("overline", "overline(${x})"),
+    ("underline", "underline(${x})"),
+    ("accent", "accent(${x}, ${hat})"),
+];
+
 /// Complete field accesses.
 fn complete_field_accesses(ctx: &mut CompletionContext) -> bool {
     // Behind an expression plus dot: "emoji.|".
@@ -333,11 +996,9 @@ fn complete_field_accesses(ctx: &mut CompletionContext) -> bool {
         if prev.is::<ast::Expr>();
         if prev.parent_kind() != Some(SyntaxKind::Markup) ||
            prev.prev_sibling_kind() == Some(SyntaxKind::Hash);
-        if let Some(value) = analyze_expr(ctx.world, &prev).into_iter().next();
         then {
             ctx.from = ctx.cursor;
-            field_access_completions(ctx, &value);
-            return true;
+            return target_field_completions(ctx, &prev) || ctx.trigger == Some('.');
         }
     }
 
@@ -348,30 +1009,152 @@ fn complete_field_accesses(ctx: &mut CompletionContext) -> bool {
         if prev.kind() == SyntaxKind::Dot;
         if let Some(prev_prev) = prev.prev_sibling();
         if prev_prev.is::<ast::Expr>();
-        if let Some(value) = analyze_expr(ctx.world, &prev_prev).into_iter().next();
         then {
             ctx.from = ctx.leaf.offset();
-            field_access_completions(ctx, &value);
-            return true;
+            ctx.to = ctx.leaf.range().end;
+            return target_field_completions(ctx, &prev_prev);
         }
     }
 
     false
 }
 
-/// Add completions for all fields on a value.
-fn field_access_completions(ctx: &mut CompletionContext, value: &Value) {
-    for (name, value) in value.ty().scope().iter() {
-        ctx.value_completion(Some(name.clone()), value, true, None);
+/// Add completions for the fields of a field access target. If its value
+/// can't be determined, its type is inferred instead: "let f(x) = rgb(x).|".
+fn target_field_completions(ctx: &mut CompletionContext, target: &LinkedNode) -> bool {
+    let values = analyze_expr(ctx.world, target);
+    if !values.is_empty() {
+        union_field_completions(ctx, &values);
+        if values.iter().any(|value| matches!(value, Value::Content(_))) {
+            postfix_completions(ctx, target);
+        }
+        return true;
     }
 
-    if let Some(scope) = value.scope() {
-        for (name, value) in scope.iter() {
-            ctx.value_completion(Some(name.clone()), value, true, None);
-        }
+    if let Some(elem) =
+        shown_element(ctx, target).or_else(|| queried_element(ctx, target))
+    {
+        type_field_completions(ctx, Type::of::<Content>());
+        element_field_completions(ctx, elem, &Dict::new());
+        postfix_completions(ctx, target);
+        return true;
     }
 
-    for &(method, args) in mutable_methods_on(value.ty()) {
+    let Some(ty) = infer_type(ctx, target) else { return false };
+    type_field_completions(ctx, ty);
+    if ty == Type::of::<Content>() {
+        postfix_completions(ctx, target);
+    }
+    true
+}
+
+/// Add completions that wrap a content expression in a function instead of
+/// accessing one of its fields: "body.strong" becomes "strong(body)".
+fn postfix_completions(ctx: &mut CompletionContext, target: &LinkedNode) {
+    let expr = &ctx.text[target.range()];
+
+    // The target and the dot are removed along with the field's replacement.
+    let edit = (target.offset(), ctx.from, EcoString::new());
+    for (name, docs) in [
+        ("strong", "Strongly emphasize the content."),
+        ("emph", "Emphasize the content."),
+        ("underline", "Underline the content."),
+        ("box", "Put the content into an inline box."),
+    ] {
+        ctx.completions.push(Completion {
+            kind: CompletionKind::Syntax,
+            label: name.into(),
+            apply: Some(eco_format!("{name}({expr})")),
+            detail: Some(eco_format!("{docs} Wraps it as `{name}({expr})`.")),
+            documentation: None,
+            score: 0.0,
+            matched: vec![],
+            additional_edits: vec![edit.clone()],
+        });
+    }
+}
+
+/// Infer the type of an expression from the types that functions and methods
+/// return: "(1, 2, 3).map(x => x).|".
+///
+/// Calls whose return type depends on their arguments, like an array's
+/// `first`, have no inferred type.
+fn infer_type(ctx: &CompletionContext, node: &LinkedNode) -> Option<Type> {
+    let values = analyze_expr(ctx.world, node);
+    if let Some(first) = values.first() {
+        let ty = first.ty();
+        return values.iter().all(|value| value.ty() == ty).then_some(ty);
+    }
+
+    let call = match node.cast::<ast::Expr>()? {
+        ast::Expr::Array(_) => return Some(Type::of::<Array>()),
+        ast::Expr::Dict(_) => return Some(Type::of::<Dict>()),
+        ast::Expr::FuncCall(call) => call,
+        _ => return None,
+    };
+
+    let func = resolve_callee(ctx, call.callee()).or_else(|| {
+        // A method call: Look the method up on the type of its target.
+        let ast::Expr::FieldAccess(access) = call.callee() else { return None };
+        let target = node.children().next()?.children().next()?;
+        match infer_type(ctx, &target)?.scope().get(&access.field())? {
+            Value::Func(func) => Some(func.clone()),
+            _ => None,
+        }
+    })?;
+
+    match func.returns()? {
+        CastInfo::Type(ty) => Some(*ty),
+        _ => None,
+    }
+}
+
+/// Add completions for the fields of all values an expression may have. Fields
+/// that only some of their types have are marked as such:
+/// "for x in ("a", (1, 2)) { x.| }".
+fn union_field_completions(ctx: &mut CompletionContext, values: &[Value]) {
+    let start = ctx.completions.len();
+    let mut types = vec![];
+    let mut owners = vec![];
+    for value in values {
+        let ty = value.ty();
+        if !types.contains(&ty) {
+            types.push(ty);
+        }
+
+        field_access_completions(ctx, value);
+        owners.resize(ctx.completions.len() - start, ty);
+    }
+
+    let mut merged: Vec<(Completion, Vec<Type>)> = vec![];
+    for (completion, ty) in ctx.completions.drain(start..).zip(owners) {
+        match merged.iter_mut().find(|(prev, _)| prev.label == completion.label) {
+            Some((_, owners)) if !owners.contains(&ty) => owners.push(ty),
+            Some(_) => {}
+            None => merged.push((completion, vec![ty])),
+        }
+    }
+
+    for (mut completion, owners) in merged {
+        if owners.len() < types.len() {
+            let names: Vec<_> = owners.iter().map(|ty| ty.short_name()).collect();
+            let note = eco_format!("Only on {}.", repr::separated_list(&names, "and"));
+            completion.detail = Some(match completion.detail {
+                Some(detail) => eco_format!("{detail} {note}"),
+                None => note,
+            });
+        }
+        ctx.completions.push(completion);
+    }
+}
+
+/// Add completions for the methods of a type.
+fn type_field_completions(ctx: &mut CompletionContext, ty: Type) {
+    for (name, value) in ty.scope().iter() {
+        ctx.value_completion(Some(name.clone()), value, true, None);
+    }
+
+    for &(method, args) in mutable_methods_on(ty) {
         ctx.completions.push(Completion {
             kind: CompletionKind::Func,
             label: method.into(),
@@ -381,8 +1164,23 @@ fn field_access_completions(ctx: &mut CompletionContext, value: &Value) {
                 eco_format!("{method}()${{}}")
             }),
             detail: None,
+            documentation: None,
+            score: 0.0,
+            matched: vec![],
+            additional_edits: vec![],
         })
     }
+}
+
+/// Add completions for all fields on a value.
+fn field_access_completions(ctx: &mut CompletionContext, value: &Value) {
+    type_field_completions(ctx, value.ty());
+
+    if let Some(scope) = value.scope() {
+        for (name, value) in scope.iter() {
+            ctx.value_completion(Some(name.clone()), value, true, None);
+        }
+    }
 
     for &field in fields_on(value.ty()) {
         // Complete the field name along with its value. Notes:
@@ -400,6 +1198,7 @@ fn field_access_completions(ctx: &mut CompletionContext, value: &Value) {
 
     match value {
         Value::Symbol(symbol) => {
+            // Modifiers come sorted, so the list doesn't change between calls.
             for modifier in symbol.modifiers() {
                 if let Ok(modified) = symbol.clone().modified(modifier) {
                     ctx.completions.push(Completion {
@@ -407,14 +1206,20 @@ fn field_access_completions(ctx: &mut CompletionContext, value: &Value) {
                         label: modifier.into(),
                         apply: None,
                         detail: None,
+                        documentation: None,
+                        score: 0.0,
+                        matched: vec![],
+                        additional_edits: vec![],
                     });
                 }
             }
         }
         Value::Content(content) => {
-            for (name, value) in content.fields() {
-                ctx.value_completion(Some(name.into()), &value, false, None);
+            let fields = content.fields();
+            for (name, value) in fields.iter() {
+                ctx.value_completion(Some(name.clone().into()), value, false, None);
             }
+            element_field_completions(ctx, content.elem(), &fields);
         }
         Value::Dict(dict) => {
             for (name, value) in dict.iter() {
@@ -428,6 +1233,10 @@ fn field_access_completions(ctx: &mut CompletionContext, value: &Value) {
                     label: name.clone(),
                     apply: None,
                     detail: None,
+                    documentation: None,
+                    score: 0.0,
+                    matched: vec![],
+                    additional_edits: vec![],
                 })
             }
         }
@@ -435,11 +1244,146 @@ fn field_access_completions(ctx: &mut CompletionContext, value: &Value) {
     }
 }
 
+/// Add completions for the fields that content of an element can have, but
+/// that aren't among the `present` ones: "it.level".
+fn element_field_completions(ctx: &mut CompletionContext, elem: Element, present: &Dict) {
+    for param in elem.params() {
+        if present.contains(param.name) {
+            continue;
+        }
+
+        ctx.completions.push(Completion {
+            kind: CompletionKind::Param,
+            label: param.name.into(),
+            apply: None,
+            detail: Some(plain_docs_sentence(param.docs)),
+            documentation: Some(param.docs.into()),
+            score: 0.0,
+            matched: vec![],
+            additional_edits: vec![],
+        });
+    }
+}
+
+/// Find the element whose content a show rule's transformation receives as
+/// the given parameter: "show heading: it => it.|".
+fn shown_element(ctx: &CompletionContext, node: &LinkedNode) -> Option<Element> {
+    let ident = node.cast::<ast::Ident>()?;
+    let mut ancestor = node.parent();
+    while let Some(closure) = ancestor {
+        if let Some(v) = closure.cast::<ast::Closure>() {
+            if let Some(ast::Param::Pos(ast::Pattern::Normal(ast::Expr::Ident(param)))) =
+                v.params().children().next()
+            {
+                if param.get() == ident.get() {
+                    let rule = closure.parent()?.cast::<ast::ShowRule>()?;
+                    if rule.transform().span() != closure.span() {
+                        return None;
+                    }
+
+                    return selector_element(ctx, rule.selector()?);
+                }
+            }
+        }
+
+        ancestor = closure.parent();
+    }
+
+    None
+}
+
+/// Find the element whose content a query's result holds, for a call that
+/// picks one of its results: "query(heading, loc).first().|".
+fn queried_element(ctx: &CompletionContext, node: &LinkedNode) -> Option<Element> {
+    let call = node.cast::<ast::FuncCall>()?;
+    let ast::Expr::FieldAccess(access) = call.callee() else { return None };
+    if !matches!(access.field().as_str(), "first" | "last" | "at") {
+        return None;
+    }
+
+    // Queries are often bound to a variable first: "let hs = query(..)".
+    let mut target = node.children().next()?.children().next()?;
+    while target.is::<ast::Ident>() {
+        target = binding_init(ctx, &target)?;
+    }
+
+    let query = target.cast::<ast::FuncCall>()?;
+    let callee = target.children().next()?;
+    let Some(Value::Func(func)) = resolve_global(ctx, &callee) else { return None };
+    if Some(&Value::Func(func)) != ctx.global.get("query") {
+        return None;
+    }
+
+    let selector = query.args().items().find_map(|arg| match arg {
+        ast::Arg::Pos(expr) => Some(expr),
+        _ => None,
+    })?;
+
+    selector_element(ctx, selector)
+}
+
+/// Find the element that a selector selects. A selector may be narrowed:
+/// "heading.where(level: 1)".
+fn selector_element(ctx: &CompletionContext, selector: ast::Expr) -> Option<Element> {
+    let selector = match selector {
+        ast::Expr::FuncCall(call) => match call.callee() {
+            ast::Expr::FieldAccess(access) if access.field().as_str() == "where" => {
+                access.target()
+            }
+            _ => return None,
+        },
+        selector => selector,
+    };
+
+    resolve_callee(ctx, selector)?.element()
+}
+
+/// Resolve an identifier to the global value it refers to, also through
+/// variables it is bound to: "let q = query". Identifiers that local bindings
+/// shadow otherwise aren't resolved.
+fn resolve_global(ctx: &CompletionContext, node: &LinkedNode) -> Option<Value> {
+    let ident = node.cast::<ast::Ident>()?;
+    if let Some(init) = binding_init(ctx, node) {
+        return resolve_global(ctx, &init);
+    }
+
+    if definition(ctx.world, ctx.source, node.range().end).is_some() {
+        return None;
+    }
+
+    ctx.global.get(&ident).cloned()
+}
+
+/// Find the expression that a variable was bound to with a let binding:
+/// "let hs = query(heading, loc)".
+fn binding_init<'a>(
+    ctx: &CompletionContext<'a>,
+    node: &LinkedNode,
+) -> Option<LinkedNode<'a>> {
+    let (source, start, _) = definition(ctx.world, ctx.source, node.range().end)?;
+    if source.id() != ctx.source.id() {
+        return None;
+    }
+
+    let ident = LinkedNode::new(ctx.source.root()).leaf_at(start + 1)?;
+    let binding = ident.parent()?;
+    let v = binding.cast::<ast::LetBinding>()?;
+    match v.kind() {
+        ast::LetBindingKind::Normal(ast::Pattern::Normal(ast::Expr::Ident(name)))
+            if name.span() == ident.span() =>
+        {
+            binding.find(v.init()?.span())
+        }
+        _ => None,
+    }
+}
+
 /// Complete half-finished labels.
 fn complete_open_labels(ctx: &mut CompletionContext) -> bool {
     // A label anywhere in code: "(<la|".
     if ctx.leaf.kind().is_error() && ctx.leaf.text().starts_with('<') {
         ctx.from = ctx.leaf.offset() + 1;
+        ctx.to = ctx.leaf.range().end;
         ctx.label_completions();
         return true;
     }
@@ -467,6 +1411,12 @@ fn complete_imports(ctx: &mut CompletionContext) -> bool {
         }
     }
 
+    // Behind a wildcard, more items are redundant:
+    // "#import "path.typ": *, |".
+    if behind_import_wildcard(&ctx.leaf) {
+        return true;
+    }
+
     // Behind an import list:
     // "#import "path.typ": |",
     // "#import "path.typ": a, b, |".
@@ -494,6 +1444,7 @@ fn complete_imports(ctx: &mut CompletionContext) -> bool {
         if let Some(source) = grand.children().find(|child| child.is::<ast::Expr>());
         then {
             ctx.from = ctx.leaf.offset();
+            ctx.to = ctx.leaf.range().end;
             import_item_completions(ctx, items, &source);
             return true;
         }
@@ -502,6 +1453,27 @@ fn complete_imports(ctx: &mut CompletionContext) -> bool {
     false
 }
 
+/// Whether a node follows a wildcard import and a comma, which isn't part of
+/// the import anymore: "#import "path.typ": *, |".
+fn behind_import_wildcard(leaf: &LinkedNode) -> bool {
+    let mut comma = false;
+    let mut sibling = Some(leaf.clone());
+    while let Some(node) = sibling {
+        if node.text() == "," && !comma {
+            comma = true;
+        } else if !node.kind().is_trivia() && !node.kind().is_error() {
+            return comma
+                && matches!(
+                    node.cast::<ast::ModuleImport>().and_then(|import| import.imports()),
+                    Some(ast::Imports::Wildcard)
+                );
+        }
+        sibling = node.prev_sibling();
+    }
+
+    false
+}
+
 /// Add completions for all exports of a module.
 fn import_item_completions<'a>(
     ctx: &mut CompletionContext<'a>,
@@ -522,6 +1494,186 @@ fn import_item_completions<'a>(
     }
 }
 
+/// Complete file paths of imports and includes, one directory at a time:
+/// "#import "chapters/|"".
+fn complete_file_paths(ctx: &mut CompletionContext) -> bool {
+    if_chain! {
+        if matches!(
+            ctx.leaf.parent_kind(),
+            Some(SyntaxKind::ModuleImport | SyntaxKind::ModuleInclude)
+        );
+        if ctx.leaf.kind() == SyntaxKind::Str;
+        if ctx.leaf.offset() < ctx.cursor && ctx.cursor < ctx.leaf.range().end;
+        let typed = &ctx.text[ctx.leaf.offset() + 1..ctx.cursor];
+        if !typed.starts_with('@');
+        then {
+            let dir = typed.rfind('/').map_or(0, |i| i + 1);
+            ctx.from = ctx.cursor - typed.len() + dir;
+            ctx.file_path_completions(&typed[..dir], &["typ"]);
+            return true;
+        }
+    }
+
+    // In a path whose string isn't closed yet. It ends the import early, so
+    // the path is parsed as markup: "#import "chap|".
+    if_chain! {
+        if let Some(quote) = match ctx.leaf.kind() {
+            SyntaxKind::SmartQuote => Some(ctx.leaf.clone()),
+            SyntaxKind::Text => ctx.leaf.prev_sibling(),
+            _ => None,
+        };
+        if quote.kind() == SyntaxKind::SmartQuote;
+        if quote.range().end <= ctx.cursor;
+        if let Some(error) = quote.prev_sibling();
+        if error.kind().is_error();
+        if let Some(import) = error.prev_sibling();
+        if matches!(import.kind(), SyntaxKind::ModuleImport | SyntaxKind::ModuleInclude);
+        if import.children().last().is_some_and(|last| last.kind().is_error());
+        let typed = &ctx.text[quote.range().end..ctx.cursor];
+        if !typed.starts_with('@');
+        then {
+            let dir = typed.rfind('/').map_or(0, |i| i + 1);
+            ctx.from = ctx.cursor - typed.len() + dir;
+            ctx.to = ctx.leaf.range().end.max(ctx.cursor);
+            let start = ctx.completions.len();
+            ctx.file_path_completions(&typed[..dir], &["typ"]);
+
+            // Files finish the path, so they close its string.
+            for completion in &mut ctx.completions[start..] {
+                if !completion.label.ends_with('/') {
+                    completion.apply = Some(eco_format!("{}\"", completion.label));
+                }
+            }
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Whether the cursor is directly behind a number without a unit.
+fn is_bare_number(ctx: &CompletionContext) -> bool {
+    matches!(ctx.leaf.kind(), SyntaxKind::Int | SyntaxKind::Float)
+        && ctx.leaf.range().end == ctx.cursor
+}
+
+/// Complete escape sequences behind a backslash in a string: "{ "a\|" }".
+/// The string may be unterminated because the backslash escapes its quote.
+fn complete_string_escapes(ctx: &mut CompletionContext) -> bool {
+    let in_str = ctx.leaf.kind() == SyntaxKind::Str
+        || (ctx.leaf.kind().is_error() && ctx.leaf.text().starts_with('"'));
+    if !in_str || ctx.leaf.offset() >= ctx.cursor {
+        return false;
+    }
+
+    // An escaped backslash doesn't start an escape sequence: "a\\|".
+    let inner = &ctx.text[ctx.leaf.offset() + 1..ctx.cursor];
+    let backslashes = inner.len() - inner.trim_end_matches('\\').len();
+    if backslashes % 2 == 0 {
+        return false;
+    }
+
+    ctx.from = ctx.cursor - 1;
+    string_escape_completions(ctx);
+    true
+}
+
+/// Add completions for escape sequences in strings.
+#[rustfmt::skip]
+fn string_escape_completions(ctx: &mut CompletionContext) {
+    ctx.snippet_completion(
+        "\\n",
+        "\\n",
+        "Inserts a newline.",
+    );
+
+    ctx.snippet_completion(
+        "\\t",
+        "\\t",
+        "Inserts a tab.",
+    );
+
+    ctx.snippet_completion(
+        "\\\"",
+        "\\\"",
+        "Inserts a double quote.",
+    );
+
+    ctx.snippet_completion(
+        "\\\\",
+        "\\\\",
+        "Inserts a backslash.",
+    );
+
+    ctx.snippet_completion(
+        "\\u{...}",
+        "\\u{${}}",
+        "Inserts a Unicode codepoint by its hexadecimal value.",
+    );
+}
+
+/// Complete the names in a destructuring pattern with the keys of the
+/// dictionary that is destructured: "let (wi|) = size".
+fn complete_destructuring(ctx: &mut CompletionContext) -> bool {
+    if !matches!(
+        ctx.leaf.kind(),
+        SyntaxKind::LeftParen | SyntaxKind::Comma | SyntaxKind::Ident
+    ) && !ctx.leaf.kind().is_trivia()
+    {
+        return false;
+    }
+
+    // A single name in parentheses isn't parsed as destructuring.
+    let Some(parent) = ctx.leaf.parent() else { return false };
+    let (binding, pattern) = match parent.kind() {
+        SyntaxKind::Destructuring => match parent.parent() {
+            Some(binding) => (binding, Some(parent)),
+            None => return false,
+        },
+        SyntaxKind::LetBinding
+            if ctx.leaf.kind() == SyntaxKind::Ident
+                && ctx.leaf.prev_sibling_kind() == Some(SyntaxKind::LeftParen) =>
+        {
+            (parent, None)
+        }
+        _ => return false,
+    };
+
+    let Some(init) = binding.cast::<ast::LetBinding>().and_then(|v| v.init()) else {
+        return false;
+    };
+
+    // Names that are already bound by the pattern.
+    let present: Vec<EcoString> = pattern
+        .into_iter()
+        .flat_map(|pattern| pattern.children())
+        .filter(|child| child.span() != ctx.leaf.span())
+        .filter_map(|child| match child.cast::<ast::DestructuringKind>()? {
+            ast::DestructuringKind::Normal(ast::Expr::Ident(ident)) => {
+                Some(ident.get().clone())
+            }
+            ast::DestructuringKind::Named(named) => Some(named.name().get().clone()),
+            _ => None,
+        })
+        .collect();
+
+    if ctx.leaf.kind() == SyntaxKind::Ident {
+        (ctx.from, ctx.to) = (ctx.leaf.offset(), ctx.leaf.range().end);
+    }
+
+    let Some(init) = binding.find(init.span()) else { return true };
+    for value in analyze_expr(ctx.world, &init) {
+        let Value::Dict(dict) = value else { continue };
+        for (key, value) in dict.iter() {
+            if is_ident(key) && !present.iter().any(|name| name == key.as_str()) {
+                ctx.value_completion(Some(key.clone().into()), value, false, None);
+            }
+        }
+    }
+
+    true
+}
+
 /// Complete set and show rules.
 fn complete_rules(ctx: &mut CompletionContext) -> bool {
     // We don't want to complete directly behind the keyword.
@@ -549,10 +1701,10 @@ fn complete_rules(ctx: &mut CompletionContext) -> bool {
     if_chain! {
         if let Some(prev) = ctx.leaf.prev_leaf();
         if matches!(prev.kind(), SyntaxKind::Colon);
-        if matches!(prev.parent_kind(), Some(SyntaxKind::ShowRule));
+        if let Some(rule) = prev.parent().and_then(|parent| parent.cast::<ast::ShowRule>());
         then {
             ctx.from = ctx.cursor;
-            show_rule_recipe_completions(ctx);
+            show_rule_recipe_completions(ctx, rule.selector());
             return true;
         }
     }
@@ -562,15 +1714,87 @@ fn complete_rules(ctx: &mut CompletionContext) -> bool {
 
 /// Add completions for all functions from the global scope.
 fn set_rule_completions(ctx: &mut CompletionContext) {
-    ctx.scope_completions(true, |value| {
-        matches!(
-            value,
-            Value::Func(func) if func.params()
-                .unwrap_or_default()
-                .iter()
-                .any(|param| param.settable),
-        )
+    ctx.scope_completions(true, is_settable);
+
+    // Local bindings come without docs and are hardly ever settable, so only
+    // keep the documented element functions.
+    ctx.completions.retain(|completion| {
+        matches!(completion.kind, CompletionKind::Func) && completion.detail.is_some()
     });
+
+    // Most set rules style one of a few elements, so these come first.
+    const COMMON: &[&str] = &["text", "par", "page", "heading"];
+    for completion in &mut ctx.completions {
+        if let Some(i) = COMMON.iter().position(|&name| completion.label == name) {
+            completion.score += (COMMON.len() - i) as f32 * 0.1;
+        }
+    }
+
+    // Modules can hold settable elements, too: "set math.|".
+    for (name, value) in ctx.global.iter() {
+        if let Value::Module(module) = value {
+            if module.scope().iter().any(|(_, member)| is_settable(member)) {
+                ctx.completions.push(Completion {
+                    kind: CompletionKind::Constant,
+                    label: name.clone(),
+                    apply: Some(eco_format!("{name}.")),
+                    detail: Some("Module with settable elements.".into()),
+                    documentation: None,
+                    score: 0.0,
+                    matched: vec![],
+                    additional_edits: vec![],
+                });
+            }
+        }
+    }
+}
+
+/// Complete dotted set rule targets: "set math.|", "set math.e|".
+fn complete_set_targets(ctx: &mut CompletionContext) -> bool {
+    if_chain! {
+        if match ctx.leaf.kind() {
+            SyntaxKind::Dot => ctx.leaf.range().end == ctx.cursor,
+            SyntaxKind::Ident => ctx.leaf.prev_sibling_kind() == Some(SyntaxKind::Dot),
+            _ => false,
+        };
+        if let Some(parent) = ctx.leaf.parent();
+        if parent.parent_kind() == Some(SyntaxKind::SetRule);
+        if let Some(access) = parent.cast::<ast::FieldAccess>();
+        if let ast::Expr::Ident(target) = access.target();
+        if let Some(value) = resolve_access_target(ctx, &target);
+        if let Some(scope) = value.scope();
+        then {
+            let prefix = if ctx.leaf.kind() == SyntaxKind::Ident {
+                ctx.from = ctx.leaf.offset();
+                ctx.to = ctx.leaf.range().end;
+                ctx.leaf.text().clone()
+            } else {
+                ctx.from = ctx.cursor;
+                EcoString::new()
+            };
+
+            for (name, value) in scope.iter() {
+                if name.starts_with(prefix.as_str()) && is_settable(value) {
+                    ctx.value_completion(Some(name.clone()), value, true, None);
+                }
+            }
+
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Whether a value is a function with settable parameters.
+fn is_settable(value: &Value) -> bool {
+    matches!(
+        value,
+        Value::Func(func) if func.params()
+            .unwrap_or_default()
+            .iter()
+            .any(|param| param.settable),
+    )
 }
 
 /// Add completions for selectors.
@@ -582,6 +1806,40 @@ fn show_rule_selector_completions(ctx: &mut CompletionContext) {
 
     ctx.enrich("", ": ");
 
+    // Selecting only the elements with certain fields is less common than
+    // selecting all of them: "show heading.where(level: 1): |".
+    let mut filtered = vec![];
+    for completion in &ctx.completions {
+        let Some(Value::Func(func)) = ctx.global.get(&completion.label) else { continue };
+        let fields: Vec<_> = func
+            .params()
+            .unwrap_or_default()
+            .iter()
+            .filter(|param| param.settable)
+            .map(|param| param.name)
+            .collect();
+        let Some(first) = fields.first() else { continue };
+        let examples: Vec<_> =
+            fields.iter().take(3).map(|field| eco_format!("`{field}`")).collect();
+        filtered.push(Completion {
+            kind: CompletionKind::Func,
+            label: eco_format!("{}.where", completion.label),
+            apply: Some(eco_format!(
+                "{}.where(${{{first}}}: ${{value}}): ${{}}",
+                completion.label
+            )),
+            detail: Some(eco_format!(
+                "Selects only the elements with certain fields, like {}.",
+                repr::separated_list(&examples, "or")
+            )),
+            documentation: None,
+            score: 0.0,
+            matched: completion.matched.clone(),
+            additional_edits: vec![],
+        });
+    }
+    ctx.completions.extend(filtered);
+
     ctx.snippet_completion(
         "text selector",
         "\"${text}\": ${}",
@@ -595,25 +1853,90 @@ fn show_rule_selector_completions(ctx: &mut CompletionContext) {
     );
 }
 
+/// Whether a show rule selector matches text rather than an element:
+/// "show "hi": |", "show regex("\d+"): |".
+fn is_text_selector(selector: ast::Expr) -> bool {
+    match selector {
+        ast::Expr::Str(_) => true,
+        ast::Expr::FuncCall(call) => {
+            matches!(call.callee(), ast::Expr::Ident(ident) if ident.as_str() == "regex")
+        }
+        _ => false,
+    }
+}
+
+/// The name of the element that a show rule selector selects: "heading",
+/// "heading.where(level: 1)", "math.equation".
+fn selected_element_name(selector: ast::Expr) -> Option<EcoString> {
+    match selector {
+        ast::Expr::Ident(ident) => Some(ident.get().clone()),
+        ast::Expr::FieldAccess(access) => Some(access.field().get().clone()),
+        ast::Expr::FuncCall(call) => match call.callee() {
+            ast::Expr::FieldAccess(access) if access.field().as_str() == "where" => {
+                selected_element_name(access.target())
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 /// Add completions for recipes.
-fn show_rule_recipe_completions(ctx: &mut CompletionContext) {
-    ctx.snippet_completion(
-        "replacement",
-        "[${content}]",
-        "Replace the selected element with content.",
-    );
+///
+/// Text is mostly replaced with other text, while elements are mostly
+/// replaced with content, so the more likely replacement comes first.
+fn show_rule_recipe_completions(
+    ctx: &mut CompletionContext,
+    selector: Option<ast::Expr>,
+) {
+    let text = selector.is_some_and(is_text_selector);
+    let content = |ctx: &mut CompletionContext| {
+        ctx.snippet_completion(
+            "replacement",
+            "[${content}]",
+            "Replace the selected element with content.",
+        );
+    };
 
-    ctx.snippet_completion(
-        "replacement (string)",
-        "\"${text}\"",
-        "Replace the selected element with a string of text.",
-    );
+    let string = |ctx: &mut CompletionContext| {
+        ctx.snippet_completion(
+            "replacement (string)",
+            "\"${text}\"",
+            "Replace the selected element with a string of text.",
+        );
+    };
 
-    ctx.snippet_completion(
-        "transformation",
-        "element => [${content}]",
-        "Transform the element with a function.",
-    );
+    if text {
+        string(ctx);
+        content(ctx);
+    } else {
+        content(ctx);
+        string(ctx);
+    }
+
+    let likely = if text { "replacement (string)" } else { "replacement" };
+    for completion in &mut ctx.completions {
+        if completion.label == likely {
+            completion.score += 0.1;
+        }
+    }
+
+    // Name the transformed element after what it is: "h" for a heading.
+    let binding = selector
+        .and_then(selected_element_name)
+        .and_then(|name| name.chars().next())
+        .map_or(EcoString::from("element"), EcoString::from);
+
+    ctx.completions.push(Completion {
+        kind: CompletionKind::Syntax,
+        label: "transformation".into(),
+        apply: Some(eco_format!("{binding} => [${{content}}]")),
+        detail: Some("Transform the element with a function.".into()),
+        documentation: None,
+        score: 0.0,
+        matched: vec![],
+        additional_edits: vec![],
+    });
 
     ctx.scope_completions(false, |value| matches!(value, Value::Func(_)));
 }
@@ -643,16 +1966,29 @@ fn complete_params(ctx: &mut CompletionContext) -> bool {
         }
     };
 
-    // Find the piece of syntax that decides what we're completing.
+    // Find the piece of syntax that decides what we're completing. An
+    // unclosed parenthesis is an error: "set text(|".
+    let unclosed = |node: &LinkedNode| node.kind().is_error() && node.text() == "(";
     let mut deciding = ctx.leaf.clone();
     while !matches!(
         deciding.kind(),
         SyntaxKind::LeftParen | SyntaxKind::Comma | SyntaxKind::Colon
-    ) {
+    ) && !unclosed(&deciding)
+    {
         let Some(prev) = deciding.prev_leaf() else { break };
         deciding = prev;
     }
 
+    // The deciding syntax must belong to the argument list itself rather than
+    // to an argument's value: "grid(columns: (1fr, 2fr) |)".
+    let owner = match deciding.kind() {
+        SyntaxKind::Colon => deciding.parent().and_then(|named| named.parent()),
+        _ => deciding.parent(),
+    };
+    if owner.map(|owner| owner.span()) != Some(args.span()) {
+        return false;
+    }
+
     // Parameter values: "func(param:|)", "func(param: |)".
     if_chain! {
         if deciding.kind() == SyntaxKind::Colon;
@@ -663,6 +1999,11 @@ fn complete_params(ctx: &mut CompletionContext) -> bool {
                 ctx.from = ctx.cursor.min(next.offset());
             }
 
+            // A string is replaced as a whole: "text(weight: "b|")".
+            if ctx.leaf.kind() == SyntaxKind::Str {
+                ctx.to = ctx.leaf.range().end;
+            }
+
             named_param_value_completions(ctx, callee, &param);
             return true;
         }
@@ -670,13 +2011,18 @@ fn complete_params(ctx: &mut CompletionContext) -> bool {
 
     // Parameters: "func(|)", "func(hi|)", "func(12,|)".
     if_chain! {
-        if matches!(deciding.kind(), SyntaxKind::LeftParen | SyntaxKind::Comma);
+        if matches!(deciding.kind(), SyntaxKind::LeftParen | SyntaxKind::Comma)
+            || unclosed(&deciding);
         if deciding.kind() != SyntaxKind::Comma || deciding.range().end < ctx.cursor;
         then {
             if let Some(next) = deciding.next_leaf() {
                 ctx.from = ctx.cursor.min(next.offset());
             }
 
+            if ctx.leaf.kind() == SyntaxKind::Ident {
+                ctx.to = ctx.leaf.range().end;
+            }
+
             param_completions(ctx, callee, set, args);
             return true;
         }
@@ -692,79 +2038,969 @@ fn param_completions<'a>(
     set: bool,
     args: ast::Args<'a>,
 ) {
-    let Some(func) = resolve_global_callee(ctx, callee) else { return };
+    let Some(func) = resolve_callee(ctx, callee) else { return };
     let Some(params) = func.params() else { return };
 
-    // Exclude named arguments which are already present.
+    // When the name of an existing named argument is edited, its colon and
+    // value are kept: "text(we|: bold)".
+    let renaming = ctx.text[ctx.to..].trim_start().starts_with(':');
+
+    if !set && !renaming {
+        contextual_arg_completions(ctx, &func);
+    }
+
+    // Exclude named arguments which are already present, except for the one
+    // being edited.
     let exclude: Vec<_> = args
         .items()
         .filter_map(|arg| match arg {
-            ast::Arg::Named(named) => Some(named.name()),
+            ast::Arg::Named(named) if named.name().span() != ctx.leaf.span() => {
+                Some(named.name())
+            }
             _ => None,
         })
         .collect();
 
+    // Parameters which earlier set rules for the same target already set are
+    // still offered, but only after all others.
+    let preset = if set { preceding_set_args(ctx, callee) } else { HashSet::new() };
+    let mut deferred = vec![];
+    let start = ctx.completions.len();
+
+    // Only values for the next positional parameter are offered, and a
+    // variadic one takes all remaining arguments: "place(top, |)".
+    let count = positional_args_before(ctx);
+    let next = params
+        .iter()
+        .filter(|param| param.positional)
+        .enumerate()
+        .find(|&(i, param)| i == count || (i < count && param.variadic))
+        .map(|(_, param)| param.name);
+
     for param in params {
         if exclude.iter().any(|ident| ident.as_str() == param.name) {
             continue;
         }
 
-        if set && !param.settable {
+        if set && !param.settable {
+            continue;
+        }
+
+        if param.named {
+            // Alternative descriptions are easily forgotten, but important
+            // for accessibility, so they come first.
+            if func.name() == Some("image") && param.name == "alt" {
+                ctx.completions.insert(
+                    start,
+                    Completion {
+                        kind: CompletionKind::Param,
+                        label: param.name.into(),
+                        apply: Some(if renaming {
+                            "alt".into()
+                        } else {
+                            "alt: \"${description}\"".into()
+                        }),
+                        detail: Some(
+                            "Describes the image for readers who can't see it.".into(),
+                        ),
+                        documentation: Some(param.docs.into()),
+                        score: 0.5,
+                        matched: vec![],
+                        additional_edits: vec![],
+                    },
+                );
+                continue;
+            }
+
+            let mut detail = plain_docs_sentence(param.docs);
+            if let Some(category) = param_category(&func, param.name) {
+                detail = eco_format!("[{category}] {detail}");
+            }
+
+            // Short defaults give a reference point for overriding them.
+            if let Some(default) = param.default.map(|default| default().repr()) {
+                if default.chars().count() <= 16 {
+                    write!(detail, " (default: {default})").unwrap();
+                }
+            }
+
+            // Parameters which conflict with present ones are still offered,
+            // but only after all others.
+            let conflict = exclude
+                .iter()
+                .find(|ident| params_conflict(&func, param.name, ident.as_str()));
+            if let Some(other) = conflict {
+                write!(detail, " Conflicts with `{}`.", other.as_str()).unwrap();
+            }
+
+            if preset.contains(param.name) {
+                detail.push_str(" Already set above.");
+            }
+
+            // Settable parameters are the ones most commonly configured.
+            let deferred_param = preset.contains(param.name) || conflict.is_some();
+            let completion = Completion {
+                kind: CompletionKind::Param,
+                label: param.name.into(),
+                apply: Some(if renaming {
+                    param.name.into()
+                } else {
+                    eco_format!("{}: ${{}}", param.name)
+                }),
+                detail: Some(detail),
+                documentation: Some(param.docs.into()),
+                score: if param.settable && !deferred_param { 0.5 } else { 0.0 },
+                matched: vec![],
+                additional_edits: vec![],
+            };
+
+            if deferred_param {
+                deferred.push(completion);
+            } else {
+                ctx.completions.push(completion);
+            }
+        }
+
+        if param.positional && !renaming && next == Some(param.name) {
+            ctx.cast_completions(&param.input);
+            layout_callback_completions(ctx, param);
+        }
+    }
+
+    ctx.completions.extend(deferred);
+
+    if ctx.before.ends_with(',') {
+        ctx.enrich(" ", "");
+    }
+}
+
+/// Count the positional arguments before the one being completed.
+fn positional_args_before(ctx: &CompletionContext) -> usize {
+    let mut node = ctx.leaf.clone();
+    while node.kind() != SyntaxKind::Args {
+        let Some(parent) = node.parent() else { return 0 };
+        node = parent.clone();
+    }
+
+    node.children()
+        .filter(|child| child.range().end <= ctx.from)
+        .filter(|child| matches!(child.cast::<ast::Arg>(), Some(ast::Arg::Pos(_))))
+        .count()
+}
+
+/// Add completions for paper sizes, along with their dimensions.
+fn paper_completions(ctx: &mut CompletionContext) {
+    for paper in Paper::ALL {
+        let docs = eco_format!(
+            "{} × {}",
+            repr::format_float(paper.width().to_mm(), Some(1), false, ""),
+            repr::format_float(paper.height().to_mm(), Some(1), false, "mm"),
+        );
+        let value = Value::Str(paper.name().into());
+        ctx.value_completion(None, &value, false, Some(&docs));
+    }
+}
+
+/// Add completions for font weights, first by name and then by number.
+fn weight_completions(ctx: &mut CompletionContext, cast: &CastInfo) {
+    let mut names = vec![];
+    cast.walk(|info| {
+        if let CastInfo::Value(value @ Value::Str(_), docs) = info {
+            names.push((value.clone(), *docs));
+        }
+    });
+
+    for (value, docs) in names {
+        ctx.value_completion(None, &value, true, Some(docs));
+    }
+
+    for number in (100..=900).step_by(100) {
+        let name = FontWeight::from_number(number).into_value();
+        let docs = eco_format!("The same as {}.", name.repr());
+        ctx.value_completion(None, &Value::Int(number.into()), false, Some(&docs));
+    }
+}
+
+/// Add completions for the codes of languages with built-in support.
+fn lang_completions(ctx: &mut CompletionContext) {
+    for &(lang, name) in Lang::SUPPORTED {
+        let value = Value::Str(lang.as_str().into());
+        ctx.value_completion(None, &value, false, Some(name));
+    }
+}
+
+/// Add completions for the codes of regions with distinct conventions.
+fn region_completions(ctx: &mut CompletionContext) {
+    for &(region, name) in Region::COMMON {
+        let value = Value::Str(region.as_str().into());
+        ctx.value_completion(None, &value, false, Some(name));
+    }
+}
+
+/// Whether two parameters of a function override each other's effect when
+/// both are set.
+fn params_conflict(func: &Func, a: &str, b: &str) -> bool {
+    const CONFLICTS: &[(&str, &str, &str)] = &[
+        ("page", "paper", "width"),
+        ("page", "paper", "height"),
+        ("grid", "gutter", "column-gutter"),
+        ("grid", "gutter", "row-gutter"),
+        ("table", "gutter", "column-gutter"),
+        ("table", "gutter", "row-gutter"),
+    ];
+
+    let name = func.name().unwrap_or_default();
+    CONFLICTS
+        .iter()
+        .any(|&(func, x, y)| func == name && ((x, y) == (a, b) || (y, x) == (a, b)))
+}
+
+/// The category of a parameter of a function with many of them, so that
+/// related parameters can be told apart at a glance.
+fn param_category(func: &Func, param: &str) -> Option<&'static str> {
+    if func.name() != Some("text") {
+        return None;
+    }
+
+    Some(match param {
+        "font" | "fallback" | "style" | "weight" | "stretch" | "size" => "font",
+        "fill" | "stroke" => "color",
+        "tracking" | "spacing" | "cjk-latin-spacing" | "baseline" | "overhang"
+        | "top-edge" | "bottom-edge" => "spacing",
+        "lang" | "region" | "script" | "dir" | "hyphenate" => "language",
+        "kerning"
+        | "alternates"
+        | "stylistic-set"
+        | "ligatures"
+        | "discretionary-ligatures"
+        | "historical-ligatures"
+        | "number-type"
+        | "number-width"
+        | "slashed-zero"
+        | "fractions"
+        | "features" => "features",
+        _ => return None,
+    })
+}
+
+/// Add completions that the positional arguments before the cursor make
+/// likely: "rgb(r, g, b, |)".
+fn contextual_arg_completions(ctx: &mut CompletionContext, func: &Func) {
+    let mut node = ctx.leaf.clone();
+    while node.kind() != SyntaxKind::Args {
+        let Some(parent) = node.parent() else { return };
+        node = parent.clone();
+    }
+
+    let before: Vec<_> = node
+        .children()
+        .filter(|child| child.range().end <= ctx.cursor)
+        .filter_map(|child| child.get().cast::<ast::Expr>())
+        .collect();
+
+    // After three integer components, an RGB color is only missing its alpha.
+    if func.name() == Some("rgb")
+        && before.len() == 3
+        && before.iter().all(|expr| matches!(expr, ast::Expr::Int(_)))
+    {
+        ctx.value_completion(None, &Value::Int(255), false, Some("Fully opaque."));
+    }
+}
+
+/// Collect the names of the arguments which set rules preceding the cursor
+/// already set for the given target.
+fn preceding_set_args(ctx: &CompletionContext, target: ast::Expr) -> HashSet<EcoString> {
+    let target = target.to_untyped().clone().into_text();
+    let mut names = HashSet::new();
+
+    let mut ancestor = Some(ctx.leaf.clone());
+    while let Some(node) = &ancestor {
+        let mut sibling = node.prev_sibling();
+        while let Some(node) = &sibling {
+            if_chain! {
+                if let Some(rule) = node.cast::<ast::SetRule>();
+                if rule.target().to_untyped().clone().into_text() == target;
+                then {
+                    names.extend(rule.args().items().filter_map(|arg| match arg {
+                        ast::Arg::Named(named) => Some(named.name().get().clone()),
+                        _ => None,
+                    }));
+                }
+            }
+
+            sibling = node.prev_sibling();
+        }
+
+        ancestor = node.parent().cloned();
+    }
+
+    names
+}
+
+/// Add completions for the values of a named function parameter.
+fn named_param_value_completions<'a>(
+    ctx: &mut CompletionContext<'a>,
+    callee: ast::Expr<'a>,
+    name: &str,
+) {
+    let Some(func) = resolve_callee(ctx, callee) else { return };
+    let Some(param) = func.param(name) else { return };
+    if !param.named {
+        return;
+    }
+
+    // Units behind a bare number: "func(param: 12|)".
+    if is_bare_number(ctx) {
+        ctx.from = ctx.cursor;
+        ctx.unit_completions(Some(&param.input));
+        return;
+    }
+
+    let start = ctx.completions.len();
+    match (func.name().unwrap_or_default(), name) {
+        ("page", "paper") => paper_completions(ctx),
+        ("text", "lang") => lang_completions(ctx),
+        ("text", "region") => region_completions(ctx),
+        ("text", "weight") => weight_completions(ctx, &param.input),
+        _ => ctx.cast_completions(&param.input),
+    }
+
+    if name == "font" {
+        ctx.font_completions();
+    }
+
+    curated_param_value_completions(ctx, &func, name);
+    layout_callback_completions(ctx, param);
+
+    // Toggles aren't self-explanatory, so tell what they switch. Values
+    // without docs of their own fall back to the parameter's docs.
+    let docs = plain_docs_sentence(param.docs);
+    for completion in &mut ctx.completions[start..] {
+        let Some(detail) = &completion.detail else { continue };
+        if detail.is_empty() {
+            completion.detail = Some(docs.clone());
+        } else if matches!(completion.kind, CompletionKind::Syntax)
+            && matches!(completion.label.as_str(), "true" | "false")
+        {
+            let on = completion.label == "true";
+            completion.detail = Some(match toggle_docs(&func, name, on) {
+                Some(toggle) => toggle.into(),
+                None => eco_format!("{detail} {docs}"),
+            });
+        }
+    }
+
+    // Offer the default value first, so that it is easy to reset to it.
+    if_chain! {
+        if let Some(default) = param.default.map(|default| default().repr());
+        if let Some(i) = ctx.completions[start..]
+            .iter()
+            .position(|completion| completion.label == default);
+        then {
+            let mut completion = ctx.completions.remove(start + i);
+            completion.detail = Some(match completion.detail {
+                Some(detail) => eco_format!("{detail} (default)"),
+                None => "(default)".into(),
+            });
+            completion.score += 0.1;
+            ctx.completions.insert(start, completion);
+        }
+    }
+
+    if ctx.before.ends_with(':') {
+        ctx.enrich(" ", "");
+    }
+}
+
+/// Describe what turning a toggle on or off does, for toggles whose effect
+/// the parameter docs don't spell out.
+fn toggle_docs(func: &Func, name: &str, on: bool) -> Option<&'static str> {
+    Some(match (func.name()?, name, on) {
+        ("quote", "block", true) => "Sets the quote apart as an indented block.",
+        ("quote", "block", false) => "Runs the quote inline with the surrounding text.",
+        ("quote", "quotes", true) => "Wraps the quote in double quotes.",
+        ("quote", "quotes", false) => "Shows the quote without double quotes.",
+        _ => return None,
+    })
+}
+
+/// Add completions for glyphs of the `sym` module, as content: "[•]".
+fn glyph_completions(ctx: &mut CompletionContext, names: &[&str]) {
+    let Some(scope) = ctx.global.get("sym").and_then(Value::scope) else { return };
+    for name in names {
+        let mut parts = name.split('.');
+        let Some(Value::Symbol(symbol)) = parts.next().and_then(|base| scope.get(base))
+        else {
+            continue;
+        };
+        let Ok(symbol) =
+            parts.try_fold(symbol.clone(), |symbol, modifier| symbol.modified(modifier))
+        else {
+            continue;
+        };
+
+        let c = symbol.get();
+        ctx.completions.push(Completion {
+            kind: CompletionKind::Symbol(c),
+            label: eco_format!("[{c}]"),
+            apply: None,
+            detail: Some(eco_format!("sym.{name}")),
+            documentation: None,
+            score: 0.0,
+            matched: vec![],
+            additional_edits: vec![],
+        });
+    }
+}
+
+/// Add hand-picked completions for parameters whose most useful values
+/// can't be derived from their cast info alone.
+fn curated_param_value_completions(ctx: &mut CompletionContext, func: &Func, name: &str) {
+    match (func.name().unwrap_or_default(), name) {
+        ("list", "marker") => {
+            ctx.snippet_completion(
+                "marker",
+                "[${•}]",
+                "A single marker for all nesting levels.",
+            );
+            ctx.snippet_completion(
+                "marker array",
+                "([${•}], [${◦}], [${▪}])",
+                "Cycles through the markers, one per nesting level.",
+            );
+
+            glyph_completions(
+                ctx,
+                &[
+                    "bullet",
+                    "square.filled.tiny",
+                    "triangle.filled.small.r",
+                    "dash.en",
+                    "dash.em",
+                ],
+            );
+        }
+        ("terms", "separator") => {
+            glyph_completions(ctx, &["colon", "dash.en", "dash.em", "arrow.r"]);
+        }
+        ("page", "background" | "foreground") => {
+            ctx.snippet_completion(
+                "full-page fill",
+                "rect(fill: ${color}, width: 100%, height: 100%)",
+                "Covers the whole page with a color.",
+            );
+            ctx.snippet_completion(
+                "full-page image",
+                "image(\"${path}\", width: 100%, height: 100%)",
+                "Covers the whole page with an image.",
+            );
+        }
+        ("text" | "super" | "sub", "baseline") => {
+            ctx.length_completion("-0.2em", Em::new(-0.2).into(), "Shifts the text up.");
+            ctx.length_completion("0.2em", Em::new(0.2).into(), "Shifts the text down.");
+        }
+        ("raw", "syntaxes") => {
+            ctx.path_completions(&["sublime-syntax"], "A syntax definition file.");
+        }
+        ("cell", "colspan" | "rowspan") => {
+            let unit = if name == "colspan" { "columns" } else { "rows" };
+            for span in 2..=3 {
+                let docs = eco_format!("Spans {span} {unit}.");
+                ctx.value_completion(None, &Value::Int(span), false, Some(&docs));
+            }
+        }
+        ("page", "margin") => {
+            ctx.length_completion("1in", Abs::inches(1.0).into(), "A narrow margin.");
+            ctx.length_completion("2.5cm", Abs::cm(2.5).into(), "A common margin.");
+        }
+        ("enum", "number-align") => {
+            for (align, docs) in [
+                (HAlignment::End + VAlignment::Top, "Aligns numbers at their end."),
+                (HAlignment::Start + VAlignment::Top, "Aligns numbers at their start."),
+                (
+                    HAlignment::Start + VAlignment::Bottom,
+                    "Aligns numbers with the last line of their item.",
+                ),
+            ] {
+                ctx.value_completion(None, &align.into_value(), false, Some(docs));
+            }
+        }
+        ("enum", "numbering") => {
+            for (pattern, docs) in [
+                ("1.a.i.", "Numbers, then letters, then roman numerals."),
+                ("I.A.1.", "Roman numerals, then capital letters, then numbers."),
+            ] {
+                let docs =
+                    eco_format!("{docs} The counting symbols apply to nested enums.");
+                ctx.value_completion(
+                    None,
+                    &Value::Str(pattern.into()),
+                    false,
+                    Some(&docs),
+                );
+            }
+        }
+        ("footnote", "numbering") => {
+            ctx.value_completion(
+                None,
+                &Value::Str("*".into()),
+                false,
+                Some("Marks footnotes with symbols: *, †, ‡, and so on."),
+            );
+        }
+        ("outline", "fill") => {
+            ctx.snippet_completion("repeat[.]", "repeat[.]", "A dotted leader.");
+            ctx.snippet_completion(
+                "custom leader",
+                "repeat[${.}]",
+                "Repeats custom content up to the page number.",
+            );
+        }
+        ("outline", "depth") => {
+            for (depth, docs) in [
+                (1, "Only lists top-level headings."),
+                (2, "Lists headings up to the second level."),
+                (3, "Lists headings up to the third level."),
+            ] {
+                ctx.value_completion(None, &Value::Int(depth), false, Some(docs));
+            }
+        }
+        ("outline", "indent") => {
+            ctx.length_completion(
+                "1em",
+                Em::new(1.0).into(),
+                "Indents each level by 1em.",
+            );
+            ctx.length_completion(
+                "2em",
+                Em::new(2.0).into(),
+                "Indents each level by 2em.",
+            );
+        }
+        ("outline", "title") => {
+            ctx.snippet_completion(
+                "custom title",
+                "[${Contents}]",
+                "Replaces the default title.",
+            );
+        }
+        _ => {}
+    }
+}
+
+/// Whether a cast accepts both a boolean toggle and a specific length.
+fn is_toggle_or_length(cast: &CastInfo) -> bool {
+    let mut toggle = false;
+    let mut length = false;
+    cast.walk(|info| {
+        if let CastInfo::Type(ty) = info {
+            toggle |= *ty == Type::of::<bool>();
+            length |= *ty == Type::of::<Length>()
+                || *ty == Type::of::<Rel<Length>>()
+                || *ty == Type::of::<Ratio>();
+        }
+    });
+    toggle && length
+}
+
+/// Add snippets for callbacks which produce content depending on sizes, for
+/// function parameters that are called during layout: "layout(|)".
+fn layout_callback_completions(ctx: &mut CompletionContext, param: &ParamInfo) {
+    let mut func = false;
+    param.input.walk(|info| {
+        func |= matches!(info, CastInfo::Type(ty) if *ty == Type::of::<Func>());
+    });
+    if !func || !param.docs.contains("layout") {
+        return;
+    }
+
+    ctx.snippet_completion(
+        "size-dependent content",
+        "size => ${}",
+        "Produces content depending on the size of the surrounding container.",
+    );
+
+    ctx.snippet_completion(
+        "measured content",
+        "size => style(styles => {\n\tlet measured = measure(${content}, styles)\n\t${}\n})",
+        "Computes the size that content would have in the current styles.",
+    );
+}
+
+/// Whether a castable offers concrete strings to choose from.
+fn has_str_choices(cast: &CastInfo) -> bool {
+    let mut choices = false;
+    cast.walk(|info| {
+        choices |= matches!(info, CastInfo::Value(Value::Str(_), _));
+    });
+    choices
+}
+
+/// Complete the elements of an array passed to a named parameter:
+/// "table(align: (left, |))".
+fn complete_param_arrays(ctx: &mut CompletionContext) -> bool {
+    // Space behind an unclosed array isn't part of it: "grid(columns: (1fr, |".
+    let unclosed = |node: &LinkedNode| node.kind().is_error() && node.text() == "(";
+    let leaf = match ctx.leaf.prev_leaf() {
+        Some(prev)
+            if ctx.leaf.kind().is_trivia()
+                && ctx.leaf.parent_kind() != prev.parent_kind()
+                && prev
+                    .parent()
+                    .and_then(|array| array.children().next())
+                    .is_some_and(|first| unclosed(&first)) =>
+        {
+            prev
+        }
+        _ => ctx.leaf.clone(),
+    };
+
+    let (callee, param, array) = if_chain! {
+        if matches!(
+            leaf.kind(),
+            SyntaxKind::LeftParen | SyntaxKind::Comma | SyntaxKind::Ident
+        ) || leaf.kind().is_trivia()
+            || unclosed(&leaf)
+            || (leaf.kind() == SyntaxKind::Error && is_ident(leaf.text()));
+        if let Some(array) = leaf.parent();
+        if matches!(
+            array.kind(),
+            SyntaxKind::Array
+                | SyntaxKind::Parenthesized
+                | SyntaxKind::Dict
+                | SyntaxKind::Destructuring
+        );
+        if let Some(named) = array.parent();
+        if let Some(param) = named.cast::<ast::Named>();
+        if let Some(args) = named.parent();
+        if args.kind() == SyntaxKind::Args;
+        if let Some(grand) = args.parent();
+        if let Some(callee) = match grand.cast::<ast::Expr>() {
+            Some(ast::Expr::FuncCall(call)) => Some(call.callee()),
+            Some(ast::Expr::Set(set)) => Some(set.target()),
+            _ => None,
+        };
+        then {
+            (callee, param.name(), array)
+        } else {
+            return false;
+        }
+    };
+
+    let Some(func) = resolve_callee(ctx, callee) else { return false };
+    let Some(param) = func.param(&param) else { return false };
+
+    (ctx.from, ctx.to) = match leaf.kind() {
+        SyntaxKind::Ident | SyntaxKind::Error if !unclosed(&leaf) => {
+            (leaf.offset(), leaf.range().end)
+        }
+        _ => (ctx.cursor, ctx.cursor),
+    };
+
+    if func.name() == Some("text") && param.name == "features" {
+        feature_completions(ctx, array);
+        return true;
+    }
+
+    // Keys of a dictionary, unless it's clearly an array: "(top: 1pt, |)".
+    let present: Vec<EcoString> = array
+        .children()
+        .filter_map(|child| Some(child.cast::<ast::Named>()?.name().get().clone()))
+        .collect();
+    let dict = !present.is_empty() || array.kind() != SyntaxKind::Array;
+    if dict || !array.children().any(|child| child.kind() == SyntaxKind::Comma) {
+        for (key, docs) in dict_keys(&func, param.name) {
+            if !present.iter().any(|name| name == key) {
+                ctx.completions.push(Completion {
+                    kind: CompletionKind::Param,
+                    label: key.into(),
+                    apply: Some(eco_format!("{key}: ${{}}")),
+                    detail: Some(docs.into()),
+                    documentation: None,
+                    score: 0.0,
+                    matched: vec![],
+                    additional_edits: vec![],
+                });
+            }
+        }
+    }
+
+    if !present.is_empty() {
+        return true;
+    }
+
+    let syntaxes = func.name() == Some("raw") && param.name == "syntaxes";
+    array_element_completions(ctx, &param.input);
+    if syntaxes {
+        ctx.path_completions(&["sublime-syntax"], "A syntax definition file.");
+    }
+
+    if ctx.before.ends_with(',') {
+        ctx.enrich(" ", "");
+    }
+
+    true
+}
+
+/// Add completions for OpenType feature tags, either as array elements or as
+/// keys of a dictionary: "("smcp", |)" or "("smcp": 1, |)".
+fn feature_completions(ctx: &mut CompletionContext, array: &LinkedNode) {
+    const FEATURES: &[(&str, &str)] = &[
+        ("afrc", "Alternative fractions"),
+        ("c2sc", "Small capitals from capitals"),
+        ("calt", "Contextual alternates"),
+        ("case", "Case-sensitive forms"),
+        ("clig", "Contextual ligatures"),
+        ("cpsp", "Capital spacing"),
+        ("dlig", "Discretionary ligatures"),
+        ("frac", "Fractions"),
+        ("hist", "Historical forms"),
+        ("hlig", "Historical ligatures"),
+        ("kern", "Kerning"),
+        ("liga", "Standard ligatures"),
+        ("lnum", "Lining figures"),
+        ("locl", "Localized forms"),
+        ("onum", "Oldstyle figures"),
+        ("ordn", "Ordinals"),
+        ("pcap", "Petite capitals"),
+        ("pnum", "Proportional figures"),
+        ("salt", "Stylistic alternates"),
+        ("smcp", "Small capitals"),
+        ("ss01", "Stylistic set 1"),
+        ("ss02", "Stylistic set 2"),
+        ("ss03", "Stylistic set 3"),
+        ("subs", "Subscript"),
+        ("sups", "Superscript"),
+        ("swsh", "Swash"),
+        ("titl", "Titling"),
+        ("tnum", "Tabular figures"),
+        ("unic", "Unicase"),
+        ("zero", "Slashed zero"),
+    ];
+
+    // An empty pair of parentheses becomes a dictionary because a single
+    // string in parentheses isn't an array.
+    let dict = array.kind() != SyntaxKind::Array
+        || !array.children().any(|child| child.kind() == SyntaxKind::Comma);
+
+    let present: Vec<EcoString> = array
+        .children()
+        .filter_map(|child| {
+            if let Some(named) = child.cast::<ast::Named>() {
+                return Some(named.name().get().clone());
+            }
+            let key = match child.cast::<ast::Keyed>() {
+                Some(pair) => pair.key(),
+                None => child.cast::<ast::Expr>()?,
+            };
+            match key {
+                ast::Expr::Str(tag) => Some(tag.get()),
+                _ => None,
+            }
+        })
+        .collect();
+
+    for &(tag, docs) in FEATURES {
+        if present.iter().any(|other| other == tag) {
             continue;
         }
 
-        if param.named {
-            ctx.completions.push(Completion {
-                kind: CompletionKind::Param,
-                label: param.name.into(),
-                apply: Some(eco_format!("{}: ${{}}", param.name)),
-                detail: Some(plain_docs_sentence(param.docs)),
-            });
+        ctx.completions.push(Completion {
+            kind: CompletionKind::Constant,
+            label: tag.into(),
+            apply: Some(if dict {
+                eco_format!("\"{tag}\": ${{1}}")
+            } else {
+                eco_format!("\"{tag}\"")
+            }),
+            detail: Some(docs.into()),
+            documentation: None,
+            score: 0.0,
+            matched: vec![],
+            additional_edits: vec![],
+        });
+    }
+}
+
+/// Complete within array and dictionary literals: "(|)", "(a: 1, |)". As a
+/// positional argument, a collection's elements are completed with what the
+/// parameter accepts: "gradient.linear((|))".
+fn complete_collection(ctx: &mut CompletionContext) -> bool {
+    if !ctx.explicit
+        || !(ctx.leaf.kind().is_trivia()
+            || matches!(ctx.leaf.kind(), SyntaxKind::LeftParen | SyntaxKind::Comma))
+    {
+        return false;
+    }
+
+    let Some(collection) = ctx.leaf.parent().cloned() else { return false };
+    if !matches!(
+        collection.kind(),
+        SyntaxKind::Array | SyntaxKind::Dict | SyntaxKind::Parenthesized
+    ) {
+        return false;
+    }
+
+    ctx.from = ctx.cursor;
+
+    if_chain! {
+        if let Some(args) = collection.parent();
+        if args.kind() == SyntaxKind::Args;
+        if let Some(call) = args.parent().and_then(|call| call.cast::<ast::FuncCall>());
+        if let Some(func) = resolve_callee(ctx, call.callee());
+        if let Some(params) = func.params();
+        let count = positional_args_before(ctx);
+        if let Some((_, param)) = params
+            .iter()
+            .filter(|param| param.positional)
+            .enumerate()
+            .find(|&(i, param)| i == count || (i < count && param.variadic));
+        then {
+            let start = ctx.completions.len();
+            array_element_completions(ctx, &param.input);
+            if ctx.completions.len() > start {
+                return true;
+            }
         }
+    }
 
-        if param.positional {
-            ctx.cast_completions(&param.input);
+    // Pairs of a dictionary, unless it's clearly an array: "(1, |)".
+    if collection.kind() != SyntaxKind::Array
+        || !collection.children().any(|child| child.kind() == SyntaxKind::Comma)
+    {
+        ctx.snippet_completion(
+            "key: value",
+            "${key}: ${value}",
+            "Adds a pair to a dictionary.",
+        );
+    }
+
+    code_completions(ctx, false);
+    true
+}
+
+/// The keys of a dictionary that a parameter accepts, along with their docs.
+fn dict_keys(func: &Func, param: &str) -> Vec<(&'static str, &'static str)> {
+    const SIDES: &[(&str, &str)] = &[
+        ("top", "The top side."),
+        ("right", "The right side."),
+        ("bottom", "The bottom side."),
+        ("left", "The left side."),
+        ("x", "The left and right sides."),
+        ("y", "The top and bottom sides."),
+        ("rest", "All sides that aren't given otherwise."),
+    ];
+
+    const MARGIN: &[(&str, &str)] = &[
+        ("top", "The top margin."),
+        ("right", "The right margin."),
+        ("bottom", "The bottom margin."),
+        ("left", "The left margin."),
+        ("inside", "The margin at the binding of two-sided documents."),
+        ("outside", "The margin opposite to the binding of two-sided documents."),
+        ("x", "The left and right margins."),
+        ("y", "The top and bottom margins."),
+        ("rest", "All margins that aren't given otherwise."),
+    ];
+
+    const CORNERS: &[(&str, &str)] = &[
+        ("top-left", "The top-left corner."),
+        ("top-right", "The top-right corner."),
+        ("bottom-right", "The bottom-right corner."),
+        ("bottom-left", "The bottom-left corner."),
+        ("top", "The two top corners."),
+        ("right", "The two right corners."),
+        ("bottom", "The two bottom corners."),
+        ("left", "The two left corners."),
+        ("rest", "All corners that aren't given otherwise."),
+    ];
+
+    const STROKE: &[(&str, &str)] = &[
+        ("paint", "The color or gradient of the stroke."),
+        ("thickness", "The stroke's thickness."),
+        ("cap", "How the ends of the stroke are drawn."),
+        ("join", "How corners of the stroke are drawn."),
+        ("dash", "The dash pattern of the stroke."),
+        ("miter-limit", "How sharp a mitered corner can be before it is beveled."),
+    ];
+
+    const SIDE_STROKES: &[(&str, &str)] = &[
+        ("top", "The stroke of the top side."),
+        ("right", "The stroke of the right side."),
+        ("bottom", "The stroke of the bottom side."),
+        ("left", "The stroke of the left side."),
+        ("x", "The stroke of the left and right sides."),
+        ("y", "The stroke of the top and bottom sides."),
+        ("rest", "The stroke of all sides that aren't given otherwise."),
+    ];
+
+    match (func.name().unwrap_or_default(), param) {
+        ("page", "margin") => MARGIN.to_vec(),
+        (_, "inset" | "outset") => SIDES.to_vec(),
+        ("rect" | "square" | "box" | "block", "radius") => CORNERS.to_vec(),
+        ("rect" | "square" | "box" | "block", "stroke") => {
+            [SIDE_STROKES, STROKE].concat()
         }
+        (_, "stroke") => STROKE.to_vec(),
+        _ => vec![],
     }
+}
 
-    if ctx.before.ends_with(',') {
-        ctx.enrich(" ", "");
+/// Add completions for the elements of an array that a parameter accepts
+/// in place of one of its other values.
+fn array_element_completions<'a>(ctx: &mut CompletionContext<'a>, cast: &'a CastInfo) {
+    match cast {
+        CastInfo::Union(union) => {
+            for info in union {
+                array_element_completions(ctx, info);
+            }
+        }
+        CastInfo::Type(ty) if *ty == Type::of::<Array>() => {}
+        _ => ctx.cast_completions(cast),
     }
 }
 
-/// Add completions for the values of a named function parameter.
-fn named_param_value_completions<'a>(
-    ctx: &mut CompletionContext<'a>,
-    callee: ast::Expr<'a>,
-    name: &str,
-) {
-    let Some(func) = resolve_global_callee(ctx, callee) else { return };
-    let Some(param) = func.param(name) else { return };
-    if !param.named {
-        return;
+/// Express a path relative to a directory, with both being relative to the
+/// project root.
+fn relative_path(dir: &Path, path: &Path) -> EcoString {
+    let mut dir = dir.components().peekable();
+    let mut path = path.components().peekable();
+    while dir.peek().is_some() && dir.peek() == path.peek() {
+        dir.next();
+        path.next();
     }
 
-    ctx.cast_completions(&param.input);
-    if name == "font" {
-        ctx.font_completions();
+    let mut relative = EcoString::new();
+    for _ in dir {
+        relative.push_str("../");
     }
 
-    if ctx.before.ends_with(':') {
-        ctx.enrich(" ", "");
+    for (i, component) in path.enumerate() {
+        if i > 0 {
+            relative.push('/');
+        }
+        relative.push_str(&component.as_os_str().to_string_lossy());
     }
+
+    relative
 }
 
-/// Resolve a callee expression to a global function.
-fn resolve_global_callee<'a>(
-    ctx: &CompletionContext<'a>,
-    callee: ast::Expr<'a>,
-) -> Option<&'a Func> {
+/// Whether a path has one of the given extensions.
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(OsStr::to_str)
+        .is_some_and(|ext| extensions.contains(&ext))
+}
+
+/// Resolve a callee expression to a function.
+fn resolve_callee(ctx: &CompletionContext, callee: ast::Expr) -> Option<Func> {
     let value = match callee {
-        ast::Expr::Ident(ident) => ctx.global.get(&ident)?,
+        ast::Expr::Ident(ident) => ctx.global.get(&ident)?.clone(),
         ast::Expr::FieldAccess(access) => match access.target() {
-            ast::Expr::Ident(target) => match ctx.global.get(&target)? {
-                Value::Module(module) => module.field(&access.field()).ok()?,
-                Value::Func(func) => func.field(&access.field()).ok()?,
+            ast::Expr::Ident(target) => match resolve_access_target(ctx, &target)? {
+                Value::Module(module) => module.field(&access.field()).ok()?.clone(),
+                Value::Func(func) => func.field(&access.field()).ok()?.clone(),
+                Value::Type(ty) => ty.field(&access.field()).ok()?.clone(),
                 _ => return None,
             },
             _ => return None,
@@ -778,6 +3014,42 @@ fn resolve_global_callee<'a>(
     }
 }
 
+/// Resolve the target of a field access, which is either global or a module
+/// imported under a new name: "import "theme.typ" as thm", "thm.|".
+fn resolve_access_target(ctx: &CompletionContext, target: &ast::Ident) -> Option<Value> {
+    if let Some(value) = ctx.global.get(target) {
+        return Some(value.clone());
+    }
+
+    let mut ancestor = Some(ctx.leaf.clone());
+    while let Some(node) = &ancestor {
+        let mut sibling = node.prev_sibling();
+        while let Some(node) = &sibling {
+            if_chain! {
+                if let Some(import) = node.cast::<ast::ModuleImport>();
+                if import.new_name().is_some_and(|new_name| new_name.get() == target.get());
+                then {
+                    // The import's source is relative to the current file, so
+                    // find out what the target evaluates to instead.
+                    let mut root = node.clone();
+                    while let Some(parent) = root.parent() {
+                        root = parent.clone();
+                    }
+
+                    let target = root.find(target.span())?;
+                    return analyze_expr(ctx.world, &target).into_iter().next();
+                }
+            }
+
+            sibling = node.prev_sibling();
+        }
+
+        ancestor = node.parent().cloned();
+    }
+
+    None
+}
+
 /// Complete in code mode.
 fn complete_code(ctx: &mut CompletionContext) -> bool {
     if matches!(
@@ -791,9 +3063,17 @@ fn complete_code(ctx: &mut CompletionContext) -> bool {
         return false;
     }
 
+    // Units behind a bare number: "{ 12| }".
+    if ctx.explicit && is_bare_number(ctx) {
+        ctx.from = ctx.cursor;
+        ctx.unit_completions(None);
+        return true;
+    }
+
     // An existing identifier: "{ pa| }".
     if ctx.leaf.kind() == SyntaxKind::Ident {
         ctx.from = ctx.leaf.offset();
+        ctx.to = ctx.leaf.range().end;
         code_completions(ctx, false);
         return true;
     }
@@ -819,6 +3099,30 @@ fn complete_code(ctx: &mut CompletionContext) -> bool {
     false
 }
 
+/// Whether the cursor directly follows a set rule for the given element:
+/// "#set list(indent: 1em) #|".
+fn after_set_rule(ctx: &CompletionContext, target: &str) -> bool {
+    let mut sibling = ctx.leaf.prev_sibling();
+    while let Some(prev) = sibling {
+        if let Some(rule) = prev.cast::<ast::SetRule>() {
+            return matches!(
+                rule.target(),
+                ast::Expr::Ident(ident) if ident.as_str() == target
+            );
+        }
+
+        // Siblings skip trivia, so blank lines and comments may come in
+        // between, too.
+        if !matches!(prev.kind(), SyntaxKind::Hash | SyntaxKind::Semicolon) {
+            return false;
+        }
+
+        sibling = prev.prev_sibling();
+    }
+
+    false
+}
+
 /// Add completions for expression snippets.
 #[rustfmt::skip]
 fn code_completions(ctx: &mut CompletionContext, hash: bool) {
@@ -844,6 +3148,23 @@ fn code_completions(ctx: &mut CompletionContext, hash: bool) {
         "Switches into markup mode.",
     );
 
+    if ctx.explicit && at_document_start(ctx) {
+        ctx.snippet_completion(
+            "page setup",
+            "set page(paper: \"${a4}\", margin: ${2cm}, numbering: \"${1}\")",
+            "Sets up the paper size, margins, and page numbering.",
+        );
+    }
+
+    // Structure and style of lists are commonly configured together.
+    if ctx.explicit && after_set_rule(ctx, "list") {
+        ctx.snippet_completion(
+            "list text style",
+            "show list: set text(${})",
+            "Styles the text of all lists.",
+        );
+    }
+
     ctx.snippet_completion(
         "set rule",
         "set ${}",
@@ -967,10 +3288,100 @@ fn code_completions(ctx: &mut CompletionContext, hash: bool) {
     }
 }
 
+/// Create a completion for a specific value, independent of where it is
+/// inserted.
+fn value_completion(
+    label: Option<EcoString>,
+    value: &Value,
+    parens: bool,
+    docs: Option<&str>,
+) -> Completion {
+    let at = label.as_deref().map_or(false, |field| !is_ident(field));
+    let label = label.unwrap_or_else(|| value.repr());
+
+    let documentation = docs.map(Into::into).or_else(|| match value {
+        Value::Func(func) => func.docs().map(Into::into),
+        Value::Type(ty) => Some(ty.docs().into()),
+        _ => None,
+    });
+
+    let detail = docs.map(Into::into).or_else(|| match value {
+        Value::Symbol(_) => None,
+        Value::Func(func) => {
+            let docs = func.docs().map(plain_docs_sentence);
+            match (compact_signature(func), docs) {
+                (Some(signature), Some(docs)) => Some(eco_format!("{signature}\n{docs}")),
+                (signature, docs) => signature.or(docs),
+            }
+        }
+        Value::Type(ty) => Some(plain_docs_sentence(ty.docs())),
+        v => {
+            let repr = v.repr();
+            (repr.as_str() != label).then_some(repr)
+        }
+    });
+
+    let mut apply = None;
+    if parens && matches!(value, Value::Func(_)) {
+        if let Value::Func(func) = value {
+            if func
+                .params()
+                .is_some_and(|params| params.iter().all(|param| param.name == "self"))
+            {
+                apply = Some(eco_format!("{label}()${{}}"));
+            } else {
+                apply = Some(eco_format!("{label}(${{}})"));
+            }
+        }
+    } else if at {
+        apply = Some(eco_format!("at(\"{label}\")"));
+    }
+
+    Completion {
+        kind: match value {
+            Value::Func(_) => CompletionKind::Func,
+            Value::Type(_) => CompletionKind::Type,
+            Value::Symbol(s) => CompletionKind::Symbol(s.get()),
+            Value::Color(color) => {
+                CompletionKind::Color(u32::from_be_bytes(color.to_vec4_u8()))
+            }
+            _ => CompletionKind::Constant,
+        },
+        label,
+        apply,
+        detail,
+        documentation,
+        score: 0.0,
+        matched: vec![],
+        additional_edits: vec![],
+    }
+}
+
+/// The completions for the definitions in the global or math scope of a
+/// library, in the scope's order. They only depend on the library, so they are
+/// built once instead of on every keystroke.
+#[comemo::memoize]
+fn library_completions(
+    library: &Prehashed<Library>,
+    math: bool,
+    parens: bool,
+) -> Arc<Vec<Completion>> {
+    let scope = if math { library.math.scope() } else { library.global.scope() };
+    Arc::new(
+        scope
+            .iter()
+            .map(|(name, value)| {
+                value_completion(Some(name.clone()), value, parens, None)
+            })
+            .collect(),
+    )
+}
+
 /// Context for autocompletion.
 struct CompletionContext<'a> {
     world: &'a (dyn World + 'a),
     document: Option<&'a Document>,
+    source: &'a Source,
     global: &'a Scope,
     math: &'a Scope,
     text: &'a str,
@@ -979,11 +3390,18 @@ struct CompletionContext<'a> {
     leaf: LinkedNode<'a>,
     cursor: usize,
     explicit: bool,
+    trigger: Option<char>,
     from: usize,
+    to: usize,
     completions: Vec<Completion>,
     seen_casts: HashSet<u128>,
+    unimported: Option<Vec<UnimportedItem>>,
 }
 
+/// An item of an imported module that isn't imported itself: its name, its
+/// value, and the edit that imports it.
+type UnimportedItem = (EcoString, Value, (usize, usize, EcoString));
+
 impl<'a> CompletionContext<'a> {
     /// Create a new autocompletion context.
     fn new(
@@ -992,6 +3410,7 @@ impl<'a> CompletionContext<'a> {
         source: &'a Source,
         cursor: usize,
         explicit: bool,
+        trigger: Option<char>,
     ) -> Option<Self> {
         let text = source.text();
         let library = world.library();
@@ -999,6 +3418,7 @@ impl<'a> CompletionContext<'a> {
         Some(Self {
             world,
             document,
+            source,
             global: library.global.scope(),
             math: library.math.scope(),
             text,
@@ -1007,9 +3427,12 @@ impl<'a> CompletionContext<'a> {
             leaf,
             cursor,
             explicit,
+            trigger,
             from: cursor,
+            to: cursor,
             completions: vec![],
             seen_casts: HashSet::new(),
+            unimported: None,
         })
     }
 
@@ -1036,11 +3459,155 @@ impl<'a> CompletionContext<'a> {
         self.completions.push(Completion {
             kind: CompletionKind::Syntax,
             label: label.into(),
-            apply: Some(snippet.into()),
+            apply: Some(normalize_snippet(snippet)),
             detail: Some(docs.into()),
+            documentation: None,
+            score: 0.0,
+            matched: vec![],
+            additional_edits: vec![],
+        });
+    }
+
+    /// Add a completion for a preset length, offered as it is `written`, like
+    /// "2.5cm".
+    ///
+    /// Absolute lengths also list what they amount to in other units.
+    fn length_completion(&mut self, written: &str, length: Length, docs: &str) {
+        let mut detail = EcoString::from(docs);
+        if length.em == Em::zero() && length.abs != Abs::zero() {
+            let abs = length.abs;
+            let units = [
+                repr::format_float_with_unit(abs.to_inches(), "in"),
+                repr::format_float_with_unit(abs.to_pt(), "pt"),
+                repr::format_float_with_unit(abs.to_cm(), "cm"),
+            ];
+            write!(detail, " ({})", units.join(" — ")).unwrap();
+        }
+
+        self.completions.push(Completion {
+            kind: CompletionKind::Constant,
+            label: written.into(),
+            apply: None,
+            detail: Some(detail),
+            documentation: Some(docs.into()),
+            score: 0.0,
+            matched: vec![],
+            additional_edits: vec![],
         });
     }
 
+    /// The files of the current file's package other than the current file,
+    /// along with the current file's id.
+    fn project_files(&self) -> Option<(FileId, Vec<FileId>)> {
+        let id = self.leaf.span().id()?;
+        let files = self
+            .world
+            .files()
+            .iter()
+            .copied()
+            .filter(|&file| file != id && file.package() == id.package())
+            .collect();
+        Some((id, files))
+    }
+
+    /// Add completions for the paths of project files with one of the given
+    /// extensions, relative to the current file.
+    fn path_completions(&mut self, extensions: &[&str], docs: &str) {
+        let Some((id, files)) = self.project_files() else { return };
+        let dir = id.vpath().as_rootless_path().parent().unwrap_or(Path::new(""));
+        for file in files {
+            let path = file.vpath().as_rootless_path();
+            if !has_extension(path, extensions) {
+                continue;
+            }
+
+            let path = relative_path(dir, path);
+            self.completions.push(Completion {
+                kind: CompletionKind::Constant,
+                apply: Some(Str::from(path.clone()).repr()),
+                label: path,
+                detail: Some(docs.into()),
+                documentation: None,
+                score: 0.0,
+                matched: vec![],
+                additional_edits: vec![],
+            });
+        }
+    }
+
+    /// Add completions for the files with one of the given extensions and the
+    /// subdirectories in a directory relative to the current file.
+    fn file_path_completions(&mut self, dir: &str, extensions: &[&str]) {
+        let Some((id, files)) = self.project_files() else { return };
+        let dir = id.vpath().join(dir);
+        let mut seen = HashSet::new();
+        for file in files {
+            let path = file.vpath().as_rooted_path();
+            let Ok(rest) = path.strip_prefix(dir.as_rooted_path()) else { continue };
+            let mut components = rest.components();
+            let Some(first) = components.next() else { continue };
+            let name = first.as_os_str().to_string_lossy();
+            let (label, detail) = if components.next().is_some() {
+                (eco_format!("{name}/"), "A directory.")
+            } else if has_extension(rest, extensions) {
+                (name.into(), "A file.")
+            } else {
+                continue;
+            };
+
+            if seen.insert(label.clone()) {
+                self.completions.push(Completion {
+                    kind: CompletionKind::Constant,
+                    label,
+                    apply: None,
+                    detail: Some(detail.into()),
+                    documentation: None,
+                    score: 0.0,
+                    matched: vec![],
+                    additional_edits: vec![],
+                });
+            }
+        }
+    }
+
+    /// Add completions for the units that can follow a number, limited to
+    /// those of types the cast accepts, if any.
+    #[rustfmt::skip]
+    fn unit_completions(&mut self, cast: Option<&CastInfo>) {
+        let accepts = |types: &[Type]| {
+            let Some(cast) = cast else { return true };
+            let mut found = false;
+            cast.walk(|info| {
+                if let CastInfo::Type(ty) = info {
+                    found |= types.contains(ty);
+                }
+            });
+            found
+        };
+
+        let relative = Type::of::<Rel<Length>>();
+        let length = accepts(&[Type::of::<Length>(), relative]);
+        let ratio = accepts(&[Type::of::<Ratio>(), relative]);
+        let angle = accepts(&[Type::of::<Angle>()]);
+        let fraction = accepts(&[Type::of::<Fr>()]);
+
+        for (accepted, unit, docs) in [
+            (length, "pt", "Points."),
+            (length, "mm", "Millimeters."),
+            (length, "cm", "Centimeters."),
+            (length, "in", "Inches."),
+            (length, "em", "Relative to the font size."),
+            (ratio, "%", "Percent."),
+            (angle, "deg", "Degrees."),
+            (angle, "rad", "Radians."),
+            (fraction, "fr", "A fraction of the remaining space."),
+        ] {
+            if accepted {
+                self.snippet_completion(unit, unit, docs);
+            }
+        }
+    }
+
     /// Add completions for all font families.
     fn font_completions(&mut self) {
         let equation = self.before_window(25).contains("equation");
@@ -1092,14 +3659,18 @@ impl<'a> CompletionContext<'a> {
                 label: name.into(),
                 apply: Some(tags[0].into()),
                 detail: Some(repr::separated_list(&tags, " or ").into()),
+                documentation: None,
+                score: 0.0,
+                matched: vec![],
+                additional_edits: vec![],
             });
         }
     }
 
     /// Add completions for labels and references.
     fn label_completions(&mut self) {
-        let Some(document) = self.document else { return };
-        let (labels, split) = analyze_labels(document);
+        let (mut labels, mut split) =
+            self.document.map(analyze_labels).unwrap_or_default();
 
         let head = &self.text[..self.from];
         let at = head.ends_with('@');
@@ -1107,6 +3678,36 @@ impl<'a> CompletionContext<'a> {
         let close = !at && !self.after.starts_with('>');
         let citation = !at && self.before_window(15).contains("cite");
 
+        // Labels in the source which aren't in the document (yet), for
+        // example because it hasn't been compiled since they were added.
+        let mut root = self.leaf.clone();
+        while let Some(parent) = root.parent() {
+            root = parent.clone();
+        }
+
+        let mut found = vec![];
+        source_labels(&root, &mut found);
+        let mut seen: HashSet<EcoString> =
+            labels.iter().map(|(label, _)| label.as_str().into()).collect();
+        for (label, detail) in found {
+            if seen.insert(label.clone()) {
+                labels.insert(split, (Label::new(&label), detail));
+                split += 1;
+            }
+        }
+
+        // Without a document, bibliography keys are loaded from the files
+        // the source refers to.
+        if self.document.is_none() {
+            let mut keys = vec![];
+            source_bibliography_keys(self.world, &root, &mut keys);
+            for (key, detail) in keys {
+                if seen.insert(key.clone()) {
+                    labels.push((Label::new(&key), detail));
+                }
+            }
+        }
+
         let (skip, take) = if at {
             (0, usize::MAX)
         } else if citation {
@@ -1117,7 +3718,7 @@ impl<'a> CompletionContext<'a> {
 
         for (label, detail) in labels.into_iter().skip(skip).take(take) {
             self.completions.push(Completion {
-                kind: CompletionKind::Constant,
+                kind: CompletionKind::Label,
                 apply: (open || close).then(|| {
                     eco_format!(
                         "{}{}{}",
@@ -1128,6 +3729,10 @@ impl<'a> CompletionContext<'a> {
                 }),
                 label: label.as_str().into(),
                 detail,
+                documentation: None,
+                score: 0.0,
+                matched: vec![],
+                additional_edits: vec![],
             });
         }
     }
@@ -1140,50 +3745,80 @@ impl<'a> CompletionContext<'a> {
         parens: bool,
         docs: Option<&str>,
     ) {
-        let at = label.as_deref().map_or(false, |field| !is_ident(field));
-        let label = label.unwrap_or_else(|| value.repr());
-
-        let detail = docs.map(Into::into).or_else(|| match value {
-            Value::Symbol(_) => None,
-            Value::Func(func) => func.docs().map(plain_docs_sentence),
-            Value::Type(ty) => Some(plain_docs_sentence(ty.docs())),
-            v => {
-                let repr = v.repr();
-                (repr.as_str() != label).then_some(repr)
+        let mut completion = value_completion(label, value, parens, docs);
+
+        // Don't duplicate quotes that are already there and not replaced:
+        // "font: "|"".
+        if completion.apply.is_none() && completion.label.starts_with('"') {
+            let label = completion.label.as_str();
+            let mut trimmed = label;
+            if self.text[..self.from].ends_with('"') {
+                trimmed = &trimmed[1..];
             }
-        });
+            if self.text[self.to..].starts_with('"') {
+                trimmed = trimmed.strip_suffix('"').unwrap_or(trimmed);
+            }
+            if trimmed.len() < label.len() {
+                completion.apply = Some(trimmed.into());
+            }
+        }
 
-        let mut apply = None;
-        if parens && matches!(value, Value::Func(_)) {
-            if let Value::Func(func) = value {
-                if func
-                    .params()
-                    .is_some_and(|params| params.iter().all(|param| param.name == "self"))
-                {
-                    apply = Some(eco_format!("{label}()${{}}"));
-                } else {
-                    apply = Some(eco_format!("{label}(${{}})"));
+        self.completions.push(completion);
+    }
+
+    /// Find the items of modules imported at the top of the file which aren't
+    /// imported themselves, each with the edit that imports it. Existing item
+    /// lists are extended rather than duplicated.
+    fn unimported_items(&self) -> Vec<UnimportedItem> {
+        let mut root = self.leaf.clone();
+        while let Some(parent) = root.parent() {
+            root = parent.clone();
+        }
+
+        let mut items = vec![];
+        for node in root.children() {
+            let Some(import) = node.cast::<ast::ModuleImport>() else { continue };
+            let Some(source) = node.children().find(|child| child.is::<ast::Expr>())
+            else {
+                continue;
+            };
+
+            let imported: Vec<_> = match import.imports() {
+                _ if import.new_name().is_some() => continue,
+                Some(ast::Imports::Wildcard) => continue,
+                Some(ast::Imports::Items(list)) => {
+                    list.iter().map(|item| item.original_name().get().clone()).collect()
                 }
-            }
-        } else if at {
-            apply = Some(eco_format!("at(\"{label}\")"));
-        } else if label.starts_with('"') && self.after.starts_with('"') {
-            if let Some(trimmed) = label.strip_suffix('"') {
-                apply = Some(trimmed.into());
+                None => vec![],
+            };
+
+            let Some(Value::Module(module)) = analyze_import(self.world, &source) else {
+                continue;
+            };
+
+            let list =
+                node.children().find(|child| child.kind() == SyntaxKind::ImportItems);
+            for (name, value) in module.scope().iter() {
+                if imported.contains(name) {
+                    continue;
+                }
+
+                let edit = if let Some(list) = &list {
+                    // Extend the list: "#import "theme.typ": accent, callout".
+                    let end = list.range().end;
+                    let sep = if self.text[..end].ends_with(',') { " " } else { ", " };
+                    (end, end, eco_format!("{sep}{name}"))
+                } else {
+                    // Give a bare import a list: "#import "theme.typ": callout".
+                    let end = source.range().end;
+                    (end, end, eco_format!(": {name}"))
+                };
+
+                items.push((name.clone(), value.clone(), edit));
             }
         }
 
-        self.completions.push(Completion {
-            kind: match value {
-                Value::Func(_) => CompletionKind::Func,
-                Value::Type(_) => CompletionKind::Type,
-                Value::Symbol(s) => CompletionKind::Symbol(s.get()),
-                _ => CompletionKind::Constant,
-            },
-            label,
-            apply,
-            detail,
-        });
+        items
     }
 
     /// Add completions for a castable.
@@ -1248,6 +3883,27 @@ impl<'a> CompletionContext<'a> {
                         "A custom HSLA color.",
                     );
                     self.scope_completions(false, |value| value.ty() == *ty);
+                } else if *ty == Type::of::<Fr>() {
+                    for (fr, docs) in [
+                        (1.0, "Takes up all remaining space."),
+                        (2.0, "Takes up twice the share of remaining space of `1fr`."),
+                    ] {
+                        let value = Value::Fraction(Fr::new(fr));
+                        self.value_completion(None, &value, false, Some(docs));
+                    }
+                    self.completions.push(Completion {
+                        kind: CompletionKind::Type,
+                        label: ty.long_name().into(),
+                        apply: Some("${1}fr".into()),
+                        detail: Some(
+                            "A share of the remaining space, distributed among all fractions."
+                                .into(),
+                        ),
+                        documentation: None,
+                        score: 0.0,
+                        matched: vec![],
+                        additional_edits: vec![],
+                    });
                 } else if *ty == Type::of::<Label>() {
                     self.label_completions()
                 } else if *ty == Type::of::<Func>() {
@@ -1258,29 +3914,67 @@ impl<'a> CompletionContext<'a> {
                     );
                 } else {
                     self.completions.push(Completion {
-                        kind: CompletionKind::Syntax,
+                        kind: CompletionKind::Type,
                         label: ty.long_name().into(),
                         apply: Some(eco_format!("${{{ty}}}")),
                         detail: Some(eco_format!("A value of type {ty}.")),
+                        documentation: None,
+                        score: 0.0,
+                        matched: vec![],
+                        additional_edits: vec![],
                     });
                     self.scope_completions(false, |value| value.ty() == *ty);
                 }
             }
             CastInfo::Union(union) => {
+                // A generic string placeholder would drown out concrete string
+                // choices like bibliography style names.
+                let choices = has_str_choices(cast);
+                let start = self.completions.len();
                 for info in union {
+                    if choices
+                        && matches!(info, CastInfo::Type(ty) if *ty == Type::of::<Str>())
+                    {
+                        continue;
+                    }
                     self.cast_completions(info);
                 }
+
+                // Booleans next to lengths look unrelated without explanation.
+                if is_toggle_or_length(cast) {
+                    for completion in &mut self.completions[start..] {
+                        let detail = match completion.label.as_str() {
+                            "false" => "Disabled.".into(),
+                            "true" => "Enabled, with the default length.".into(),
+                            "length" | "relative length" | "ratio" => {
+                                eco_format!(
+                                    "Enabled, with a specific {}.",
+                                    completion.label
+                                )
+                            }
+                            _ => continue,
+                        };
+                        completion.detail = Some(detail);
+                    }
+                }
             }
         }
     }
 
-    /// Add completions for definitions that are available at the cursor.
-    ///
-    /// Filters the global/math scope with the given filter.
-    fn scope_completions(&mut self, parens: bool, filter: impl Fn(&Value) -> bool) {
+    /// Collect the names that local bindings visible at the cursor define.
+    fn local_bindings(&self) -> BTreeSet<EcoString> {
         let mut defined = BTreeSet::new();
 
-        let mut ancestor = Some(self.leaf.clone());
+        // An unfinished expression continues at the cursor: "(x) => x + |".
+        let mut ancestor = match self.leaf.prev_leaf() {
+            Some(prev)
+                if self.leaf.kind().is_trivia()
+                    && prev.next_sibling_kind().is_some_and(SyntaxKind::is_error) =>
+            {
+                Some(prev)
+            }
+            _ => Some(self.leaf.clone()),
+        };
         while let Some(node) = &ancestor {
             let mut sibling = Some(node.clone());
             while let Some(node) = &sibling {
@@ -1292,7 +3986,14 @@ impl<'a> CompletionContext<'a> {
 
                 if let Some(v) = node.cast::<ast::ModuleImport>() {
                     let imports = v.imports();
+                    let new_name = v.new_name();
+                    if let Some(new_name) = new_name {
+                        defined.insert(new_name.get().clone());
+                    }
+
                     match imports {
+                        // A renamed module is only bound under its new name.
+                        None if new_name.is_some() => {}
                         None | Some(ast::Imports::Wildcard) => {
                             if let Some(value) = node
                                 .children()
@@ -1329,6 +4030,29 @@ impl<'a> CompletionContext<'a> {
                     }
                 }
 
+                // Parameters are only bound in the closure's body.
+                if let Some(v) = parent.cast::<ast::Closure>() {
+                    if node.span() == v.body().span() {
+                        for param in v.params().children() {
+                            match param {
+                                ast::Param::Pos(pattern) => {
+                                    for ident in pattern.idents() {
+                                        defined.insert(ident.get().clone());
+                                    }
+                                }
+                                ast::Param::Named(named) => {
+                                    defined.insert(named.name().get().clone());
+                                }
+                                ast::Param::Sink(spread) => {
+                                    if let Some(ident) = spread.name() {
+                                        defined.insert(ident.get().clone());
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
                 ancestor = Some(parent.clone());
                 continue;
             }
@@ -1336,6 +4060,15 @@ impl<'a> CompletionContext<'a> {
             break;
         }
 
+        defined
+    }
+
+    /// Add completions for definitions that are available at the cursor.
+    ///
+    /// Filters the global/math scope with the given filter.
+    fn scope_completions(&mut self, parens: bool, filter: impl Fn(&Value) -> bool) {
+        let defined = self.local_bindings();
+
         let in_math = matches!(
             self.leaf.parent_kind(),
             Some(SyntaxKind::Equation)
@@ -1344,22 +4077,104 @@ impl<'a> CompletionContext<'a> {
                 | Some(SyntaxKind::MathAttach)
         );
 
+        // Names need to contain the typed text as a subsequence: "#tbl|".
+        let typed = self.text.get(self.from..self.cursor).unwrap_or_default();
+
         let scope = if in_math { self.math } else { self.global };
-        for (name, value) in scope.iter() {
-            if filter(value) && !defined.contains(name) {
-                self.value_completion(Some(name.clone()), value, parens, None);
+        let prebuilt = library_completions(self.world.library(), in_math, parens);
+        for ((name, value), completion) in scope.iter().zip(prebuilt.iter()) {
+            if !filter(value) || defined.contains(name) {
+                continue;
+            }
+
+            let Some(m) = fuzzy_match(typed, name) else { continue };
+            self.completions
+                .push(Completion { matched: m.indices, ..completion.clone() });
+        }
+
+        // Items of imported modules that aren't imported themselves come with
+        // an edit that imports them: "#import "theme.typ": accent", "#cal|".
+        // Loading the modules is costly, so they are only looked at once.
+        if self.unimported.is_none() {
+            self.unimported = Some(self.unimported_items());
+        }
+
+        for (name, value, edit) in self.unimported.clone().unwrap_or_default() {
+            if !filter(&value) || defined.contains(&name) || scope.get(&name).is_some() {
+                continue;
+            }
+
+            let Some(m) = fuzzy_match(typed, &name) else { continue };
+            let len = self.completions.len();
+            self.value_completion(Some(name), &value, parens, None);
+            if let Some(completion) = self.completions.get_mut(len) {
+                completion.matched = m.indices;
+                completion.additional_edits = vec![edit];
             }
         }
 
         for name in defined {
-            if !name.is_empty() {
-                self.completions.push(Completion {
-                    kind: CompletionKind::Constant,
-                    label: name,
-                    apply: None,
-                    detail: None,
-                });
+            if name.is_empty() {
+                continue;
             }
+
+            // Local bindings shadow global ones of the same name, which are
+            // thus not offered themselves. Their description is kept, though.
+            let detail = scope.get(&name).and_then(|value| match value {
+                Value::Func(func) => func.docs().map(plain_docs_sentence),
+                Value::Type(ty) => Some(plain_docs_sentence(ty.docs())),
+                _ => None,
+            });
+
+            let Some(m) = fuzzy_match(typed, &name) else { continue };
+            self.completions.push(Completion {
+                kind: CompletionKind::Constant,
+                label: name,
+                apply: None,
+                detail,
+                documentation: None,
+                score: 0.0,
+                matched: m.indices,
+                additional_edits: vec![],
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_snippet() {
+        #[track_caller]
+        fn test(snippet: &str, normalized: &str) {
+            assert_eq!(normalize_snippet(snippet), normalized);
+        }
+
+        test("$${x}$", "$${1:x}$");
+        test("[${}]", "[${1}]");
+        test("${lhs} + ${rhs}", "${1:lhs} + ${2:rhs}");
+        test("if ${1 < 2} {${}}", "if ${1:1 < 2} {${2}}");
+        test("${x}_${2:2}", "${1:x}_${2:2}");
+        test("${a}${1:b}${c}", "${2:a}${1:b}${3:c}");
+        test("${f(${x})}", "${1:f(${2:x})}");
+        test("${1}fr", "${1}fr");
+        test("${2}, ${}", "${2}, ${1}");
+    }
+
+    #[test]
+    fn test_to_lsp_snippet() {
+        #[track_caller]
+        fn test(apply: &str, snippet: &str, format: InsertTextFormat) {
+            assert_eq!(to_lsp_snippet(apply), (snippet.into(), format));
         }
+
+        test("table", "table", InsertTextFormat::PlainText);
+        test("$x$", "$x$", InsertTextFormat::PlainText);
+        test("${lhs} + ${rhs}", "${1:lhs} + ${2:rhs}", InsertTextFormat::Snippet);
+        test("{${}}", "{${1}\\}", InsertTextFormat::Snippet);
+        test("${2} ${x}", "${2} ${1:x}", InsertTextFormat::Snippet);
+        test("$${1:x}$ \\", "\\$${1:x}\\$ \\\\", InsertTextFormat::Snippet);
     }
 }
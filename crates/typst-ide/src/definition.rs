@@ -0,0 +1,227 @@
+use std::ops::Range;
+
+use ecow::EcoString;
+use typst::foundations::Value;
+use typst::syntax::ast::{self, AstNode};
+use typst::syntax::{LinkedNode, Source, SyntaxKind};
+use typst::World;
+
+use crate::analyze::analyze_import;
+
+/// Find where the identifier at the cursor is defined.
+///
+/// Returns the source file of the definition and the range of the defining
+/// identifier in it. Names bound by let bindings, for loops, closure
+/// parameters and imports are found, and imported names are followed into the
+/// module they come from. Names from the standard library have no definition
+/// in source code and return `None`.
+pub fn definition(
+    world: &dyn World,
+    source: &Source,
+    cursor: usize,
+) -> Option<(Source, usize, usize)> {
+    let leaf = LinkedNode::new(source.root()).leaf_at(cursor)?;
+    let name: EcoString = match leaf.cast::<ast::Ident>() {
+        Some(ident) => ident.get().clone(),
+        None => leaf.cast::<ast::MathIdent>()?.get().clone(),
+    };
+
+    // Bindings are visible to their later siblings and everything within them.
+    let mut ancestor = Some(leaf.clone());
+    while let Some(node) = &ancestor {
+        let mut sibling = node.prev_sibling();
+        while let Some(prev) = &sibling {
+            if let Some(found) = sibling_definition(world, source, prev, &name) {
+                return Some(found);
+            }
+            sibling = prev.prev_sibling();
+        }
+
+        let parent = node.parent()?;
+        if let Some(found) = parent_definition(world, source, node, parent, &leaf, &name)
+        {
+            return Some(found);
+        }
+
+        ancestor = Some(parent.clone());
+    }
+
+    None
+}
+
+/// Find the definition of a name in a binding that precedes its use.
+fn sibling_definition(
+    world: &dyn World,
+    source: &Source,
+    node: &LinkedNode,
+    name: &str,
+) -> Option<(Source, usize, usize)> {
+    if let Some(v) = node.cast::<ast::LetBinding>() {
+        let ident = v.kind().idents().into_iter().find(|ident| ident.as_str() == name)?;
+        return Some(locate(source, node.find(ident.span())?.range()));
+    }
+
+    let v = node.cast::<ast::ModuleImport>()?;
+    if let Some(new_name) = v.new_name() {
+        if new_name.as_str() == name {
+            return Some(locate(source, node.find(new_name.span())?.range()));
+        }
+    }
+
+    match v.imports()? {
+        ast::Imports::Wildcard => {
+            let found = module_definition(world, source, node, name);
+            if found.is_some() {
+                return found;
+            }
+
+            // The module defines the name, but not somewhere we can point to.
+            let module = node
+                .children()
+                .find(|child| child.is::<ast::Expr>())
+                .and_then(|expr| analyze_import(world, &expr))?;
+            module.scope()?.get(name)?;
+            let star = node.children().find(|child| child.kind() == SyntaxKind::Star)?;
+            Some(locate(source, star.range()))
+        }
+        ast::Imports::Items(items) => {
+            let item = items.iter().find(|item| item.bound_name().as_str() == name)?;
+            item_definition(world, source, node, item)
+        }
+    }
+}
+
+/// Find the definition of a name that the leaf's ancestor `parent` binds for
+/// its child `node`, or that the leaf itself binds.
+fn parent_definition(
+    world: &dyn World,
+    source: &Source,
+    node: &LinkedNode,
+    parent: &LinkedNode,
+    leaf: &LinkedNode,
+    name: &str,
+) -> Option<(Source, usize, usize)> {
+    // A name where it is bound is its own definition.
+    let binds =
+        |idents: &[ast::Ident]| idents.iter().any(|ident| ident.span() == leaf.span());
+
+    if let Some(v) = parent.cast::<ast::LetBinding>() {
+        if binds(&v.kind().idents()) {
+            return Some(locate(source, leaf.range()));
+        }
+    }
+
+    if let Some(v) = parent.cast::<ast::ModuleImport>() {
+        if v.new_name().is_some_and(|new_name| new_name.span() == leaf.span()) {
+            return Some(locate(source, leaf.range()));
+        }
+
+        if let Some(ast::Imports::Items(items)) = v.imports() {
+            let item = items
+                .iter()
+                .find(|item| binds(&[item.original_name(), item.bound_name()]))?;
+            return item_definition(world, source, parent, item);
+        }
+    }
+
+    let idents = if let Some(v) = parent.cast::<ast::ForLoop>() {
+        if node.prev_sibling_kind() == Some(SyntaxKind::In) {
+            return None;
+        }
+        v.pattern().idents()
+    } else if let Some(v) = parent.cast::<ast::Closure>() {
+        let mut idents: Vec<_> = v.name().into_iter().collect();
+        for param in v.params().children() {
+            match param {
+                ast::Param::Pos(pattern) => idents.extend(pattern.idents()),
+                ast::Param::Named(named) => idents.push(named.name()),
+                ast::Param::Sink(spread) => idents.extend(spread.name()),
+            }
+        }
+
+        if binds(&idents) {
+            return Some(locate(source, leaf.range()));
+        }
+
+        // Parameters are only bound in the closure's body.
+        if node.span() != v.body().span() {
+            return None;
+        }
+        idents
+    } else {
+        return None;
+    };
+
+    let ident = idents.into_iter().rev().find(|ident| ident.as_str() == name)?;
+    Some(locate(source, parent.find(ident.span())?.range()))
+}
+
+/// Find the definition of an imported item in its module, or else the item
+/// itself.
+fn item_definition(
+    world: &dyn World,
+    source: &Source,
+    import: &LinkedNode,
+    item: ast::ImportItem,
+) -> Option<(Source, usize, usize)> {
+    module_definition(world, source, import, item.original_name().as_str())
+        .or_else(|| Some(locate(source, import.find(item.bound_name().span())?.range())))
+}
+
+/// Find the definition of a name in the module that an import loads.
+fn module_definition(
+    world: &dyn World,
+    source: &Source,
+    import: &LinkedNode,
+    name: &str,
+) -> Option<(Source, usize, usize)> {
+    let v = import.cast::<ast::ModuleImport>()?;
+
+    // Files are searched for the last top-level binding of the name.
+    if let ast::Expr::Str(path) = v.source() {
+        let path = path.get();
+        if !path.starts_with('@') {
+            if let Ok(module) = world.source(source.id().join(&path)) {
+                let range =
+                    LinkedNode::new(module.root()).children().rev().find_map(|child| {
+                        let binding = child.cast::<ast::LetBinding>()?;
+                        let ident = binding
+                            .kind()
+                            .idents()
+                            .into_iter()
+                            .find(|ident| ident.as_str() == name)?;
+                        Some(child.find(ident.span())?.range())
+                    });
+                if let Some(range) = range {
+                    return Some(locate(&module, range));
+                }
+            }
+        }
+    }
+
+    // Functions know where they were defined, also in packages.
+    let expr = import.children().find(|child| child.is::<ast::Expr>())?;
+    let Value::Func(func) = analyze_import(world, &expr)?.scope()?.get(name)?.clone()
+    else {
+        return None;
+    };
+
+    let span = func.span();
+    let module = world.source(span.id()?).ok()?;
+    let range = {
+        // Closures carry the span of their parameter list.
+        let params = LinkedNode::new(module.root()).find(span)?;
+        let closure = params.parent()?;
+        match closure.cast::<ast::Closure>().and_then(|closure| closure.name()) {
+            Some(ident) => closure.find(ident.span())?.range(),
+            None => closure.range(),
+        }
+    };
+
+    Some(locate(&module, range))
+}
+
+/// Pair a range with the source file it is in.
+fn locate(source: &Source, range: Range<usize>) -> (Source, usize, usize) {
+    (source.clone(), range.start, range.end)
+}
@@ -2,13 +2,21 @@
 
 mod analyze;
 mod complete;
+mod definition;
 mod jump;
+mod search;
+mod signature;
 mod tooltip;
 
 pub use self::analyze::analyze_labels;
-pub use self::complete::{autocomplete, Completion, CompletionKind};
+pub use self::complete::{
+    autocomplete, to_lsp_snippet, Completion, CompletionKind, InsertTextFormat,
+};
+pub use self::definition::definition;
 pub use self::jump::{jump_from_click, jump_from_cursor, Jump};
-pub use self::tooltip::{tooltip, Tooltip};
+pub use self::search::symbol_search;
+pub use self::signature::{signature_help, SignatureHelp, SignatureParam};
+pub use self::tooltip::{hover, tooltip, Hover, Tooltip};
 
 use std::fmt::Write;
 
@@ -90,3 +98,53 @@ fn summarize_font_family<'a>(variants: impl Iterator<Item = &'a FontInfo>) -> Ec
 
     detail
 }
+
+/// How a search pattern matches a candidate.
+struct FuzzyMatch {
+    /// How well the pattern matches. Higher is better.
+    score: usize,
+    /// The indices of the candidate's characters that the pattern matched.
+    indices: Vec<usize>,
+}
+
+/// Match a search pattern against a candidate, case-insensitively.
+///
+/// Exact matches rank above prefix matches, which rank above substring
+/// matches. Otherwise, the pattern's characters must appear in order and
+/// matches with fewer gaps rank higher. An empty pattern matches anything
+/// with a score of zero. Returns `None` if there is no match.
+fn fuzzy_match(pattern: &str, candidate: &str) -> Option<FuzzyMatch> {
+    let lower = |c: char| c.to_lowercase().next().unwrap_or(c);
+    let pattern: Vec<char> = pattern.chars().map(lower).collect();
+    let candidate: Vec<char> = candidate.chars().map(lower).collect();
+    if pattern.is_empty() {
+        return Some(FuzzyMatch { score: 0, indices: vec![] });
+    }
+
+    if let Some(start) =
+        candidate.windows(pattern.len()).position(|window| window == pattern)
+    {
+        let len = candidate.len().min(999);
+        let score = if candidate.len() == pattern.len() {
+            4000
+        } else if start == 0 {
+            3000 - len
+        } else {
+            2000 - len
+        };
+        return Some(FuzzyMatch {
+            score,
+            indices: (start..start + pattern.len()).collect(),
+        });
+    }
+
+    let mut indices = vec![];
+    let mut chars = candidate.iter().enumerate();
+    for p in &pattern {
+        let (i, _) = chars.find(|(_, c)| *c == p)?;
+        indices.push(i);
+    }
+
+    let gaps = indices.last().map_or(0, |last| last + 1 - pattern.len());
+    Some(FuzzyMatch { score: 1000 - gaps.min(999), indices })
+}
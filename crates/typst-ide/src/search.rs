@@ -0,0 +1,83 @@
+use std::cmp::Reverse;
+use std::collections::HashSet;
+
+use ecow::eco_format;
+use typst::foundations::{Scope, Value};
+use typst::syntax::ast::Shorthand;
+use typst::World;
+
+use crate::{fuzzy_match, Completion, CompletionKind};
+
+/// Search all symbols by name for a symbol palette.
+///
+/// Looks through the math scope and the `sym` and `emoji` modules, including
+/// all variants of each symbol. Symbols are also found by their Unicode name,
+/// like "rightwards arrow". A query consisting of just a symbol's glyph or of
+/// a shorthand for it, like `->`, finds it, too. The results are sorted by
+/// match quality, best first.
+pub fn symbol_search(world: &dyn World, query: &str) -> Vec<Completion> {
+    let library = world.library();
+    let mut seen = HashSet::new();
+    let mut matches = vec![];
+
+    let mut search = |scope: &Scope, prefix: &str| {
+        for (name, value) in scope.iter() {
+            let Value::Symbol(symbol) = value else { continue };
+            for (modifiers, c) in symbol.variants() {
+                let label = match (prefix.is_empty(), modifiers.is_empty()) {
+                    (true, true) => name.clone(),
+                    (true, false) => eco_format!("{name}.{modifiers}"),
+                    (false, true) => eco_format!("{prefix}.{name}"),
+                    (false, false) => eco_format!("{prefix}.{name}.{modifiers}"),
+                };
+
+                let shorthand = Shorthand::MARKUP_LIST
+                    .iter()
+                    .chain(Shorthand::MATH_LIST)
+                    .any(|&(shorthand, x)| x == c && shorthand == query);
+
+                let by_label = fuzzy_match(query, &label);
+                let score = if shorthand || query.chars().eq(std::iter::once(c)) {
+                    Some(usize::MAX)
+                } else {
+                    // Unicode names are long, so only whole parts of them
+                    // count as a match.
+                    let by_unicode = unicode_names2::name(c)
+                        .and_then(|name| fuzzy_match(query, &name.to_string()))
+                        .map(|m| m.score)
+                        .filter(|&score| score >= 2000);
+                    by_label.as_ref().map(|m| m.score).max(by_unicode)
+                };
+
+                if let Some(score) = score {
+                    if seen.insert((c, label.clone())) {
+                        let matched = by_label.map(|m| m.indices).unwrap_or_default();
+                        matches.push((score, label, c, matched));
+                    }
+                }
+            }
+        }
+    };
+
+    search(library.math.scope(), "");
+    for module in ["sym", "emoji"] {
+        if let Some(scope) = library.global.scope().get(module).and_then(Value::scope) {
+            search(scope, module);
+        }
+    }
+
+    matches.sort_by(|(a, x, ..), (b, y, ..)| (Reverse(a), x).cmp(&(Reverse(b), y)));
+    matches
+        .into_iter()
+        .map(|(score, label, c, matched)| Completion {
+            kind: CompletionKind::Symbol(c),
+            matched,
+            additional_edits: vec![],
+            label,
+            apply: None,
+            detail: None,
+            documentation: None,
+            score: score as f32,
+        })
+        .collect()
+}
@@ -0,0 +1,196 @@
+use std::fmt::Write;
+
+use ecow::{eco_format, EcoString};
+use if_chain::if_chain;
+use typst::foundations::{CastInfo, Func, ParamInfo, Value};
+use typst::syntax::{ast, LinkedNode, Source, SyntaxKind};
+use typst::World;
+
+use crate::analyze::analyze_expr;
+use crate::plain_docs_sentence;
+
+/// Describe the signature of the function whose arguments are being written
+/// at the cursor.
+///
+/// In nested calls, the innermost argument list around the cursor wins:
+/// "text(size: 12pt, fill: rgb(|))" helps with `rgb`.
+pub fn signature_help(
+    world: &dyn World,
+    source: &Source,
+    cursor: usize,
+) -> Option<SignatureHelp> {
+    let leaf = LinkedNode::new(source.root()).leaf_at(cursor)?;
+
+    // Find the innermost argument list that the cursor is within.
+    let mut ancestor = leaf.parent();
+    let (args, callee) = loop {
+        let node = ancestor?;
+        if_chain! {
+            if node.kind() == SyntaxKind::Args;
+            if node.offset() < cursor;
+            if cursor < node.range().end
+                || node.children().last().map(|last| last.kind())
+                    != Some(SyntaxKind::RightParen);
+            if let Some(parent) = node.parent();
+            if let Some(callee) = match parent.kind() {
+                SyntaxKind::FuncCall => parent.children().next(),
+                SyntaxKind::SetRule => {
+                    parent.children().find(|child| child.is::<ast::Expr>())
+                }
+                _ => None,
+            };
+            then {
+                break (node, callee);
+            }
+        }
+        ancestor = node.parent();
+    };
+
+    // Calls which aren't evaluated can still be to global functions.
+    let value = analyze_expr(world, &callee).into_iter().next().or_else(|| {
+        let ident = callee.cast::<ast::Ident>()?;
+        world.library().global.scope().get(&ident).cloned()
+    });
+
+    let Some(Value::Func(func)) = value else { return None };
+
+    let params = func.params()?;
+    Some(SignatureHelp {
+        label: signature_label(&func)?,
+        docs: func.docs().map(plain_docs_sentence),
+        params: params
+            .iter()
+            .map(|param| SignatureParam {
+                label: param_label(param),
+                docs: plain_docs_sentence(param.docs),
+            })
+            .collect(),
+        active: active_param(args, cursor, params),
+    })
+}
+
+/// The signature of a function, as shown while writing its arguments.
+#[derive(Debug, Clone)]
+pub struct SignatureHelp {
+    /// The whole signature, like `rgb(red, green, blue, alpha)`.
+    pub label: EcoString,
+    /// The first sentence of the function's documentation.
+    pub docs: Option<EcoString>,
+    /// The function's parameters, in order.
+    pub params: Vec<SignatureParam>,
+    /// The index of the parameter the argument at the cursor is given for.
+    pub active: Option<usize>,
+}
+
+/// A parameter in a signature.
+#[derive(Debug, Clone)]
+pub struct SignatureParam {
+    /// The parameter as written in the signature.
+    pub label: EcoString,
+    /// The first sentence of the parameter's documentation.
+    pub docs: EcoString,
+}
+
+/// Write out the signature of a function, like `rgb(red, green, blue, alpha)`.
+pub fn signature_label(func: &Func) -> Option<EcoString> {
+    let labels: Vec<_> = func.params()?.iter().map(param_label).collect();
+    Some(eco_format!("{}({})", func.name().unwrap_or_default(), labels.join(", ")))
+}
+
+/// Write out a compact signature of a function with the types of its first
+/// few parameters and its output, like `luma(lightness: int | ratio, ..) ->
+/// color`.
+pub fn compact_signature(func: &Func) -> Option<EcoString> {
+    const SHOWN: usize = 3;
+
+    let params = func.params()?;
+    let mut pieces: Vec<EcoString> = params
+        .iter()
+        .take(SHOWN)
+        .map(|param| {
+            let name = if param.variadic {
+                eco_format!("..{}", param.name)
+            } else {
+                param.name.into()
+            };
+            eco_format!("{name}: {}", cast_types(&param.input))
+        })
+        .collect();
+
+    if params.len() > SHOWN {
+        pieces.push("..".into());
+    }
+
+    let mut signature =
+        eco_format!("{}({})", func.name().unwrap_or_default(), pieces.join(", "));
+    if let Some(returns) = func.returns() {
+        write!(signature, " -> {}", cast_types(returns)).unwrap();
+    }
+
+    Some(signature)
+}
+
+/// The types a castable accepts: "length | auto".
+fn cast_types(cast: &CastInfo) -> EcoString {
+    let mut types = vec![];
+    cast.walk(|info| {
+        let name = match info {
+            CastInfo::Any => "any",
+            CastInfo::Value(value, _) => value.ty().short_name(),
+            CastInfo::Type(ty) => ty.short_name(),
+            CastInfo::Union(_) => return,
+        };
+        if !types.contains(&name) {
+            types.push(name);
+        }
+    });
+    types.join(" | ").into()
+}
+
+/// How a parameter is written in a signature.
+fn param_label(param: &ParamInfo) -> EcoString {
+    if param.variadic {
+        eco_format!("..{}", param.name)
+    } else if param.named && !param.positional {
+        eco_format!("{}:", param.name)
+    } else {
+        param.name.into()
+    }
+}
+
+/// Find the parameter that the argument at the cursor is given for. Named
+/// arguments are resolved by name, positional ones by counting.
+///
+/// An argument extends up to the next comma or the closing parenthesis, so
+/// that a value which isn't written yet belongs to it: "text(fill: |)".
+fn active_param(args: &LinkedNode, cursor: usize, params: &[ParamInfo]) -> Option<usize> {
+    let mut positional = 0;
+    for child in args.children() {
+        let Some(arg) = child.cast::<ast::Arg>() else { continue };
+        let end = child.next_sibling().map_or(args.range().end, |next| next.offset());
+        let within = child.offset() <= cursor && cursor <= end;
+        match arg {
+            ast::Arg::Named(named) if within => {
+                return params
+                    .iter()
+                    .position(|param| param.name == named.name().as_str());
+            }
+            ast::Arg::Named(_) => {}
+            _ if within => break,
+            _ if child.range().end < cursor => positional += 1,
+            _ => break,
+        }
+    }
+
+    // Variadic parameters take all remaining positional arguments.
+    for (i, param) in params.iter().enumerate() {
+        if !param.positional {
+            continue;
+        } else if positional == 0 || param.variadic {
+            return Some(i);
+        }
+        positional -= 1;
+    }
+
+    None
+}
@@ -1,4 +1,5 @@
 use std::fmt::Write;
+use std::ops::Range;
 
 use ecow::{eco_format, EcoString};
 use if_chain::if_chain;
@@ -11,6 +12,7 @@ use typst::util::{round_2, Numeric};
 use typst::World;
 
 use crate::analyze::{analyze_expr, analyze_labels};
+use crate::signature::signature_label;
 use crate::{plain_docs_sentence, summarize_font_family};
 
 /// Describe the item under the cursor.
@@ -45,6 +47,56 @@ pub enum Tooltip {
     Code(EcoString),
 }
 
+/// Describe the identifier under the cursor in detail.
+///
+/// Unlike a [tooltip], this spells out the full documentation, the value, and
+/// for functions the signature. Field accesses are resolved, so hovering over
+/// `face` in `emoji.face` previews the symbol.
+pub fn hover(world: &dyn World, source: &Source, cursor: usize) -> Option<Hover> {
+    let leaf = LinkedNode::new(source.root()).leaf_at(cursor)?;
+    let ident = match leaf.cast::<ast::Expr>()? {
+        ast::Expr::Ident(ident) => ident.get().clone(),
+        ast::Expr::MathIdent(ident) => ident.get().clone(),
+        _ => return None,
+    };
+
+    // Identifiers which aren't evaluated can still be global.
+    let value = analyze_expr(world, &leaf).into_iter().next().or_else(|| {
+        let library = world.library();
+        let scope = match leaf.parent_kind() {
+            Some(SyntaxKind::FieldAccess) if leaf.index() > 0 => return None,
+            Some(SyntaxKind::Math | SyntaxKind::MathAttach | SyntaxKind::MathFrac) => {
+                library.math.scope()
+            }
+            _ => library.global.scope(),
+        };
+        scope.get(&ident).cloned()
+    })?;
+
+    Some(Hover {
+        range: leaf.range(),
+        docs: value.docs().map(Into::into),
+        repr: value.repr(),
+        signature: match &value {
+            Value::Func(func) => signature_label(func),
+            _ => None,
+        },
+    })
+}
+
+/// A detailed description of a hovered identifier.
+#[derive(Debug, Clone)]
+pub struct Hover {
+    /// The range of the identifier in the source.
+    pub range: Range<usize>,
+    /// The full documentation of the identifier's value, if any.
+    pub docs: Option<EcoString>,
+    /// The identifier's value, as Typst code.
+    pub repr: EcoString,
+    /// The signature of the identifier's value if it is a function.
+    pub signature: Option<EcoString>,
+}
+
 /// Tooltip for a hovered expression.
 fn expr_tooltip(world: &dyn World, leaf: &LinkedNode) -> Option<Tooltip> {
     let mut ancestor = leaf;
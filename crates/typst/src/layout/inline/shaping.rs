@@ -972,8 +972,7 @@ pub(super) const END_PUNCT_PAT: &[char] = &[
 pub(super) fn is_gb_style(lang: Lang, region: Option<Region>) -> bool {
     // Most CJK variants, including zh-CN, ja-JP, zh-SG, zh-MY use GB-style punctuation,
     // while zh-HK and zh-TW use alternative style. We default to use GB-style.
-    !(lang == Lang::CHINESE
-        && matches!(region.as_ref().map(Region::as_str), Some("TW" | "HK")))
+    !(lang == Lang::CHINESE && matches!(region, Some(Region::TAIWAN | Region::HONG_KONG)))
 }
 
 /// Whether the glyph is a space.
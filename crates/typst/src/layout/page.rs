@@ -766,6 +766,11 @@ pub struct Paper {
 }
 
 impl Paper {
+    /// The name of the paper, by which it is selected.
+    pub fn name(self) -> &'static str {
+        self.name
+    }
+
     /// The width of the paper.
     pub fn width(self) -> Abs {
         Abs::mm(self.width.get())
@@ -789,6 +794,9 @@ macro_rules! papers {
                 width: Scalar::new($width),
                 height: Scalar::new($height),
             };)*
+
+            /// All predefined papers.
+            pub const ALL: &'static [Self] = &[$(Self::$var),*];
         }
 
         impl FromStr for Paper {
@@ -228,6 +228,14 @@ pub trait World {
     fn packages(&self) -> &[(PackageSpec, Option<EcoString>)] {
         &[]
     }
+
+    /// A list of all files in the project.
+    ///
+    /// This function is optional to implement. It enhances the user experience
+    /// by enabling autocompletion for file paths.
+    fn files(&self) -> &[FileId] {
+        &[]
+    }
 }
 
 /// Helper methods on [`World`] implementations.
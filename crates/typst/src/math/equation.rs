@@ -18,7 +18,7 @@ use crate::text::{
     families, variant, Font, FontFamily, FontList, FontWeight, Lang, LocalName, Region,
     TextElem,
 };
-use crate::util::{option_eq, NonZeroExt, Numeric};
+use crate::util::{NonZeroExt, Numeric};
 use crate::World;
 
 /// A mathematical equation.
@@ -262,7 +262,7 @@ impl LocalName for Packed<EquationElem> {
             Lang::ARABIC => "معادلة",
             Lang::BOKMÅL => "Ligning",
             Lang::CATALAN => "Equació",
-            Lang::CHINESE if option_eq(region, "TW") => "方程式",
+            Lang::CHINESE if region == Some(Region::TAIWAN) => "方程式",
             Lang::CHINESE => "公式",
             Lang::CZECH => "Rovnice",
             Lang::DANISH => "Ligning",
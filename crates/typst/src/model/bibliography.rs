@@ -39,7 +39,7 @@ use crate::syntax::{Span, Spanned};
 use crate::text::{
     FontStyle, Lang, LocalName, Region, SubElem, SuperElem, TextElem, WeightDelta,
 };
-use crate::util::{option_eq, NonZeroExt, PicoStr};
+use crate::util::{NonZeroExt, PicoStr};
 use crate::World;
 
 /// A bibliography / reference listing.
@@ -186,14 +186,19 @@ impl BibliographyElem {
         let mut vec = vec![];
         for elem in introspector.query(&Self::elem().select()).iter() {
             let this = elem.to_packed::<Self>().unwrap();
-            for entry in this.bibliography().entries() {
-                let key = entry.key().into();
-                let detail = entry.title().map(|title| title.value.to_str().into());
-                vec.push((key, detail))
-            }
+            vec.extend(this.bibliography().entry_keys());
         }
         vec
     }
+
+    /// Find the keys in bibliography files, given their paths and contents,
+    /// without a compiled document.
+    pub fn load_keys(
+        paths: Vec<EcoString>,
+        data: &[Bytes],
+    ) -> StrResult<Vec<(EcoString, Option<EcoString>)>> {
+        Ok(Bibliography::load(&BibliographyPaths(paths), data)?.entry_keys())
+    }
 }
 
 impl Synthesize for Packed<BibliographyElem> {
@@ -291,7 +296,7 @@ impl LocalName for Packed<BibliographyElem> {
             Lang::ARABIC => "المراجع",
             Lang::BOKMÅL => "Bibliografi",
             Lang::CATALAN => "Bibliografia",
-            Lang::CHINESE if option_eq(region, "TW") => "書目",
+            Lang::CHINESE if region == Some(Region::TAIWAN) => "書目",
             Lang::CHINESE => "参考文献",
             Lang::CZECH => "Bibliografie",
             Lang::DANISH => "Bibliografi",
@@ -408,6 +413,17 @@ impl Bibliography {
     fn entries(&self) -> impl Iterator<Item = &hayagriva::Entry> {
         self.map.values()
     }
+
+    /// The keys of all entries, along with their titles.
+    fn entry_keys(&self) -> Vec<(EcoString, Option<EcoString>)> {
+        self.entries()
+            .map(|entry| {
+                let key = entry.key().into();
+                let detail = entry.title().map(|title| title.value.to_str().into());
+                (key, detail)
+            })
+            .collect()
+    }
 }
 
 impl Debug for Bibliography {
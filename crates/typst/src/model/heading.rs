@@ -10,7 +10,7 @@ use crate::introspection::{Count, Counter, CounterUpdate, Locatable};
 use crate::layout::{BlockElem, Em, HElem, VElem};
 use crate::model::{Numbering, Outlinable, Refable, Supplement};
 use crate::text::{FontWeight, Lang, LocalName, Region, SpaceElem, TextElem, TextSize};
-use crate::util::{option_eq, NonZeroExt};
+use crate::util::NonZeroExt;
 
 /// A section heading.
 ///
@@ -245,7 +245,7 @@ impl LocalName for Packed<HeadingElem> {
             Lang::ARABIC => "الفصل",
             Lang::BOKMÅL => "Kapittel",
             Lang::CATALAN => "Secció",
-            Lang::CHINESE if option_eq(region, "TW") => "小節",
+            Lang::CHINESE if region == Some(Region::TAIWAN) => "小節",
             Lang::CHINESE => "小节",
             Lang::CZECH => "Kapitola",
             Lang::DANISH => "Afsnit",
@@ -260,7 +260,7 @@ impl LocalName for Packed<HeadingElem> {
             Lang::ITALIAN => "Sezione",
             Lang::NYNORSK => "Kapittel",
             Lang::POLISH => "Sekcja",
-            Lang::PORTUGUESE if option_eq(region, "PT") => "Secção",
+            Lang::PORTUGUESE if region == Some(Region::PORTUGAL) => "Secção",
             Lang::PORTUGUESE => "Seção",
             Lang::ROMANIAN => "Secțiunea",
             Lang::RUSSIAN => "Раздел",
@@ -12,7 +12,7 @@ use crate::layout::{BoxElem, Fr, HElem, HideElem, Length, Rel, RepeatElem, Spaci
 use crate::model::{Destination, HeadingElem, NumberingPattern, ParbreakElem, Refable};
 use crate::syntax::Span;
 use crate::text::{Lang, LinebreakElem, LocalName, Region, SpaceElem, TextElem};
-use crate::util::{option_eq, NonZeroExt};
+use crate::util::NonZeroExt;
 
 /// A table of contents, figures, or other elements.
 ///
@@ -265,7 +265,7 @@ impl LocalName for Packed<OutlineElem> {
             Lang::ARABIC => "المحتويات",
             Lang::BOKMÅL => "Innhold",
             Lang::CATALAN => "Índex",
-            Lang::CHINESE if option_eq(region, "TW") => "目錄",
+            Lang::CHINESE if region == Some(Region::TAIWAN) => "目錄",
             Lang::CHINESE => "目录",
             Lang::CZECH => "Obsah",
             Lang::DANISH => "Indhold",
@@ -280,7 +280,7 @@ impl LocalName for Packed<OutlineElem> {
             Lang::ITALIAN => "Indice",
             Lang::NYNORSK => "Innhald",
             Lang::POLISH => "Spis treści",
-            Lang::PORTUGUESE if option_eq(region, "PT") => "Índice",
+            Lang::PORTUGUESE if region == Some(Region::PORTUGAL) => "Índice",
             Lang::PORTUGUESE => "Sumário",
             Lang::ROMANIAN => "Cuprins",
             Lang::RUSSIAN => "Содержание",
@@ -119,7 +119,7 @@ impl Symbol {
         }
     }
 
-    /// Possible modifiers.
+    /// Possible modifiers, in alphabetical order.
     pub fn modifiers(&self) -> impl Iterator<Item = &str> + '_ {
         let mut set = BTreeSet::new();
         let modifiers = match &self.0 {
@@ -337,3 +337,21 @@ fn parts(modifiers: &str) -> impl Iterator<Item = &str> {
 fn contained(modifiers: &str, m: &str) -> bool {
     parts(modifiers).any(|part| part == m)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbol_modifiers_sorted() {
+        let arrow = Symbol::list(&[
+            ("r", '→'),
+            ("r.double", '⇒'),
+            ("l", '←'),
+            ("l.double", '⇐'),
+            ("b", '↓'),
+        ]);
+        let modifiers: Vec<_> = arrow.modifiers().collect();
+        assert_eq!(modifiers, ["b", "double", "l", "r"]);
+    }
+}
@@ -10,38 +10,60 @@ use crate::text::TextElem;
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Lang([u8; 3], u8);
 
-impl Lang {
-    pub const ALBANIAN: Self = Self(*b"sq ", 2);
-    pub const ARABIC: Self = Self(*b"ar ", 2);
-    pub const BOKMÅL: Self = Self(*b"nb ", 2);
-    pub const CATALAN: Self = Self(*b"ca ", 2);
-    pub const CHINESE: Self = Self(*b"zh ", 2);
-    pub const CZECH: Self = Self(*b"cs ", 2);
-    pub const DANISH: Self = Self(*b"da ", 2);
-    pub const DUTCH: Self = Self(*b"nl ", 2);
-    pub const ENGLISH: Self = Self(*b"en ", 2);
-    pub const ESTONIAN: Self = Self(*b"et ", 2);
-    pub const FILIPINO: Self = Self(*b"tl ", 2);
-    pub const FINNISH: Self = Self(*b"fi ", 2);
-    pub const FRENCH: Self = Self(*b"fr ", 2);
-    pub const GERMAN: Self = Self(*b"de ", 2);
-    pub const GREEK: Self = Self(*b"gr ", 2);
-    pub const ITALIAN: Self = Self(*b"it ", 2);
-    pub const JAPANESE: Self = Self(*b"ja ", 2);
-    pub const NYNORSK: Self = Self(*b"nn ", 2);
-    pub const POLISH: Self = Self(*b"pl ", 2);
-    pub const PORTUGUESE: Self = Self(*b"pt ", 2);
-    pub const RUSSIAN: Self = Self(*b"ru ", 2);
-    pub const SERBIAN: Self = Self(*b"sr ", 2);
-    pub const SLOVENIAN: Self = Self(*b"sl ", 2);
-    pub const SPANISH: Self = Self(*b"es ", 2);
-    pub const SWEDISH: Self = Self(*b"sv ", 2);
-    pub const TURKISH: Self = Self(*b"tr ", 2);
-    pub const UKRAINIAN: Self = Self(*b"ua ", 2);
-    pub const VIETNAMESE: Self = Self(*b"vi ", 2);
-    pub const HUNGARIAN: Self = Self(*b"hu ", 2);
-    pub const ROMANIAN: Self = Self(*b"ro ", 2);
+/// Defines the languages with built-in support, like translated terms.
+macro_rules! languages {
+    ($($name:ident: $code:literal, $english:literal;)*) => {
+        impl Lang {
+            $(pub const $name: Self = Self(pad($code), 2);)*
+
+            /// The languages with built-in support, like translated terms,
+            /// along with their English names, sorted by code.
+            pub const SUPPORTED: &'static [(Self, &'static str)] =
+                &[$((Self::$name, $english)),*];
+        }
+    };
+}
+
+languages! {
+    ARABIC: "ar", "Arabic";
+    CATALAN: "ca", "Catalan";
+    CZECH: "cs", "Czech";
+    DANISH: "da", "Danish";
+    GERMAN: "de", "German";
+    ENGLISH: "en", "English";
+    SPANISH: "es", "Spanish";
+    ESTONIAN: "et", "Estonian";
+    FINNISH: "fi", "Finnish";
+    FRENCH: "fr", "French";
+    GREEK: "gr", "Greek";
+    HUNGARIAN: "hu", "Hungarian";
+    ITALIAN: "it", "Italian";
+    JAPANESE: "ja", "Japanese";
+    BOKMÅL: "nb", "Norwegian Bokmål";
+    DUTCH: "nl", "Dutch";
+    NYNORSK: "nn", "Norwegian Nynorsk";
+    POLISH: "pl", "Polish";
+    PORTUGUESE: "pt", "Portuguese";
+    ROMANIAN: "ro", "Romanian";
+    RUSSIAN: "ru", "Russian";
+    SLOVENIAN: "sl", "Slovenian";
+    ALBANIAN: "sq", "Albanian";
+    SERBIAN: "sr", "Serbian";
+    SWEDISH: "sv", "Swedish";
+    FILIPINO: "tl", "Filipino";
+    TURKISH: "tr", "Turkish";
+    UKRAINIAN: "ua", "Ukrainian";
+    VIETNAMESE: "vi", "Vietnamese";
+    CHINESE: "zh", "Chinese";
+}
+
+/// Pad a two-letter language code to the three bytes of a [`Lang`].
+const fn pad(code: &str) -> [u8; 3] {
+    let bytes = code.as_bytes();
+    [bytes[0], bytes[1], b' ']
+}
 
+impl Lang {
     /// Return the language code as an all lowercase string slice.
     pub fn as_str(&self) -> &str {
         std::str::from_utf8(&self.0[..usize::from(self.1)]).unwrap_or_default()
@@ -84,6 +106,30 @@ cast! {
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Region([u8; 2]);
 
+/// Defines the regions whose conventions differ from others with the same
+/// language, like in translated terms or punctuation.
+macro_rules! regions {
+    ($($name:ident: $code:literal, $english:literal;)*) => {
+        impl Region {
+            $(pub const $name: Self = Self(*$code);)*
+
+            /// Regions whose conventions differ from others with the same
+            /// language, along with their English names, sorted by code.
+            pub const COMMON: &'static [(Self, &'static str)] =
+                &[$((Self::$name, $english)),*];
+        }
+    };
+}
+
+regions! {
+    SWITZERLAND: b"CH", "Switzerland";
+    SPAIN: b"ES", "Spain";
+    HONG_KONG: b"HK", "Hong Kong";
+    LIECHTENSTEIN: b"LI", "Liechtenstein";
+    PORTUGAL: b"PT", "Portugal";
+    TAIWAN: b"TW", "Taiwan";
+}
+
 impl Region {
     /// Return the region code as an all uppercase string slice.
     pub fn as_str(&self) -> &str {
@@ -176,6 +222,12 @@ mod tests {
     use super::*;
     use crate::util::option_eq;
 
+    #[test]
+    fn test_supported_sorted_by_code() {
+        assert!(Lang::SUPPORTED.windows(2).all(|w| w[0].0 < w[1].0));
+        assert!(Region::COMMON.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
     #[test]
     fn test_region_option_eq() {
         let region = Some(Region([b'U', b'S']));
@@ -22,7 +22,6 @@ use crate::text::{
     FontFamily, FontList, Hyphenate, Lang, LinebreakElem, LocalName, Region,
     SmartQuoteElem, TextElem, TextSize,
 };
-use crate::util::option_eq;
 use crate::visualize::Color;
 use crate::{syntax, World};
 
@@ -439,7 +438,7 @@ impl LocalName for Packed<RawElem> {
             Lang::ARABIC => "قائمة",
             Lang::BOKMÅL => "Utskrift",
             Lang::CATALAN => "Llistat",
-            Lang::CHINESE if option_eq(region, "TW") => "程式",
+            Lang::CHINESE if region == Some(Region::TAIWAN) => "程式",
             Lang::CHINESE => "代码",
             Lang::CZECH => "Seznam",
             Lang::DANISH => "Liste",
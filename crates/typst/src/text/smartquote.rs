@@ -197,16 +197,20 @@ impl<'s> SmartQuotes<'s> {
         region: Option<Region>,
         alternative: bool,
     ) -> Self {
-        let region = region.as_ref().map(Region::as_str);
-
         let default = ("‘", "’", "“", "”");
         let low_high = ("‚", "‘", "„", "“");
 
         let (single_open, single_close, double_open, double_close) = match lang.as_str() {
-            "de" if matches!(region, Some("CH" | "LI")) => match alternative {
-                false => ("‹", "›", "«", "»"),
-                true => low_high,
-            },
+            "de" if matches!(
+                region,
+                Some(Region::SWITZERLAND | Region::LIECHTENSTEIN)
+            ) =>
+            {
+                match alternative {
+                    false => ("‹", "›", "«", "»"),
+                    true => low_high,
+                }
+            }
             "cs" | "da" | "de" | "sk" | "sl" if alternative => ("›", "‹", "»", "«"),
             "cs" | "de" | "et" | "is" | "lt" | "lv" | "sk" | "sl" => low_high,
             "da" => ("‘", "’", "“", "”"),
@@ -214,7 +218,7 @@ impl<'s> SmartQuotes<'s> {
             "fr" => ("‹\u{00A0}", "\u{00A0}›", "«\u{00A0}", "\u{00A0}»"),
             "fi" | "sv" if alternative => ("’", "’", "»", "»"),
             "bs" | "fi" | "sv" => ("’", "’", "”", "”"),
-            "es" if matches!(region, Some("ES") | None) => ("“", "”", "«", "»"),
+            "es" if matches!(region, Some(Region::SPAIN) | None) => ("“", "”", "«", "»"),
             "hu" | "pl" | "ro" => ("’", "’", "„", "”"),
             "no" | "nb" | "nn" if alternative => low_high,
             "ru" | "no" | "nb" | "nn" | "ua" => ("’", "’", "«", "»"),
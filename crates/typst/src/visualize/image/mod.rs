@@ -27,7 +27,7 @@ use crate::loading::Readable;
 use crate::model::Figurable;
 use crate::syntax::{Span, Spanned};
 use crate::text::{families, Lang, LocalName, Region};
-use crate::util::{option_eq, Numeric};
+use crate::util::Numeric;
 use crate::visualize::Path;
 use crate::World;
 
@@ -250,7 +250,7 @@ impl LocalName for Packed<ImageElem> {
             Lang::ARABIC => "شكل",
             Lang::BOKMÅL => "Figur",
             Lang::CATALAN => "Figura",
-            Lang::CHINESE if option_eq(region, "TW") => "圖",
+            Lang::CHINESE if region == Some(Region::TAIWAN) => "圖",
             Lang::CHINESE => "图",
             Lang::CZECH => "Obrázek",
             Lang::DANISH => "Figur",
@@ -91,6 +91,13 @@ pub enum AnnotationKind {
     Hint,
     AutocompleteContains,
     AutocompleteExcludes,
+    AutocompleteApply,
+    AutocompleteOrder,
+    AutocompleteDetail,
+    Signature,
+    SymbolSearch,
+    Hover,
+    Definition,
 }
 
 impl AnnotationKind {
@@ -102,8 +109,32 @@ impl AnnotationKind {
             AnnotationKind::Hint => "Hint",
             AnnotationKind::AutocompleteContains => "Autocomplete contains",
             AnnotationKind::AutocompleteExcludes => "Autocomplete excludes",
+            AnnotationKind::AutocompleteApply => "Autocomplete apply",
+            AnnotationKind::AutocompleteOrder => "Autocomplete order",
+            AnnotationKind::AutocompleteDetail => "Autocomplete detail",
+            AnnotationKind::Signature => "Signature",
+            AnnotationKind::SymbolSearch => "Symbol search",
+            AnnotationKind::Hover => "Hover",
+            AnnotationKind::Definition => "Definition",
         }
     }
+
+    /// Whether this annotation is checked against the IDE functionality
+    /// rather than against diagnostics.
+    pub fn is_ide(self) -> bool {
+        matches!(
+            self,
+            AnnotationKind::AutocompleteContains
+                | AnnotationKind::AutocompleteExcludes
+                | AnnotationKind::AutocompleteApply
+                | AnnotationKind::AutocompleteOrder
+                | AnnotationKind::AutocompleteDetail
+                | AnnotationKind::Signature
+                | AnnotationKind::SymbolSearch
+                | AnnotationKind::Hover
+                | AnnotationKind::Definition
+        )
+    }
 }
 
 impl FromStr for AnnotationKind {
@@ -116,6 +147,13 @@ impl FromStr for AnnotationKind {
             "Hint" => AnnotationKind::Hint,
             "Autocomplete contains" => AnnotationKind::AutocompleteContains,
             "Autocomplete excludes" => AnnotationKind::AutocompleteExcludes,
+            "Autocomplete apply" => AnnotationKind::AutocompleteApply,
+            "Autocomplete order" => AnnotationKind::AutocompleteOrder,
+            "Autocomplete detail" => AnnotationKind::AutocompleteDetail,
+            "Signature" => AnnotationKind::Signature,
+            "Symbol search" => AnnotationKind::SymbolSearch,
+            "Hover" => AnnotationKind::Hover,
+            "Definition" => AnnotationKind::Definition,
             _ => return Err("invalid annotatino"),
         })
     }
@@ -182,13 +220,11 @@ pub fn parse_part_metadata(
                         continue;
                     }
 
-                    if matches!(
-                        kind,
-                        AnnotationKind::AutocompleteContains
-                            | AnnotationKind::AutocompleteExcludes
-                    ) {
+                    // Symbol searches don't depend on a cursor and hovers
+                    // apply to a whole range.
+                    if kind.is_ide() && kind != AnnotationKind::SymbolSearch {
                         if let Some(range) = range {
-                            if range.start != range.end {
+                            if range.start != range.end && kind != AnnotationKind::Hover {
                                 invalid_data.push(InvalidMetadata::InvalidAnnotation(
                                     annotation,
                                     "Error: found range in Autocomplete annotation where range.start != range.end, range.end would be ignored."
@@ -234,13 +270,20 @@ fn parse_metadata_line(line: &str) -> Option<(&str, &str)> {
 }
 
 /// Parse a quoted string.
-fn parse_string<'a>(s: &mut Scanner<'a>) -> Option<&'a str> {
+///
+/// Quotes and backslashes can be escaped with a backslash.
+fn parse_string(s: &mut Scanner) -> Option<String> {
     if !s.eat_if('"') {
         return None;
     }
-    let sub = s.eat_until('"');
-    if !s.eat_if('"') {
-        return None;
+
+    let mut sub = String::new();
+    loop {
+        match s.eat()? {
+            '"' => break,
+            '\\' => sub.push(s.eat()?),
+            c => sub.push(c),
+        }
     }
 
     Some(sub)
@@ -258,11 +301,16 @@ fn parse_num(s: &mut Scanner) -> Option<isize> {
 }
 
 /// Parse a comma-separated list of strings.
-pub fn parse_string_list(text: &str) -> HashSet<&str> {
+pub fn parse_string_list(text: &str) -> HashSet<String> {
+    parse_strings(text).into_iter().collect()
+}
+
+/// Parse a comma-separated sequence of strings, keeping their order.
+pub fn parse_strings(text: &str) -> Vec<String> {
     let mut s = Scanner::new(text);
-    let mut result = HashSet::new();
+    let mut result = vec![];
     while let Some(sub) = parse_string(&mut s) {
-        result.insert(sub);
+        result.push(sub);
         s.eat_whitespace();
         if !s.eat_if(',') {
             break;
@@ -257,6 +257,7 @@ struct TestWorld {
     library: Prehashed<Library>,
     book: Prehashed<FontBook>,
     fonts: Vec<Font>,
+    files: Vec<FileId>,
     slots: RwLock<HashMap<FileId, FileSlot>>,
 }
 
@@ -280,12 +281,35 @@ impl TestWorld {
             fonts.extend(Font::iter(data.into()));
         }
 
+        // Search for asset files, which are available under `files/`, and
+        // for the test files themselves.
+        let assets = Path::new(ASSET_DIR);
+        let files = WalkDir::new(assets.join("files"))
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .filter_map(|entry| {
+                let path = entry.path().strip_prefix(assets).ok()?;
+                Some(FileId::new(None, VirtualPath::new(path)))
+            })
+            .chain(
+                WalkDir::new(TYP_DIR)
+                    .sort_by_file_name()
+                    .into_iter()
+                    .filter_map(|e| e.ok())
+                    .filter(|entry| entry.file_type().is_file())
+                    .map(|entry| FileId::new(None, VirtualPath::new(entry.path()))),
+            )
+            .collect();
+
         Self {
             print,
             main: FileId::new(None, VirtualPath::new("main.typ")),
             library: Prehashed::new(library()),
             book: Prehashed::new(FontBook::from_fonts(&fonts)),
             fonts,
+            files,
             slots: RwLock::new(HashMap::new()),
         }
     }
@@ -331,6 +355,10 @@ impl World for TestWorld {
     fn today(&self, _: Option<i64>) -> Option<Datetime> {
         Some(Datetime::from_ymd(1970, 1, 1).unwrap())
     }
+
+    fn files(&self) -> &[FileId] {
+        &self.files
+    }
 }
 
 impl TestWorld {
@@ -362,6 +390,7 @@ impl Clone for TestWorld {
             library: self.library.clone(),
             book: self.book.clone(),
             fonts: self.fonts.clone(),
+            files: self.files.clone(),
             slots: RwLock::new(self.slots.read().unwrap().clone()),
         }
     }
@@ -649,13 +678,7 @@ fn test_part(
             let diagnostic_annotations = metadata
                 .annotations
                 .iter()
-                .filter(|a| {
-                    !matches!(
-                        a.kind,
-                        AnnotationKind::AutocompleteContains
-                            | AnnotationKind::AutocompleteExcludes
-                    )
-                })
+                .filter(|a| !a.kind.is_ide())
                 .cloned()
                 .collect::<HashSet<_>>();
 
@@ -724,24 +747,146 @@ fn test_autocomplete<'a>(
     ok: &mut bool,
     annotations: impl Iterator<Item = &'a Annotation>,
 ) {
-    for annotation in annotations.filter(|a| {
-        matches!(
-            a.kind,
-            AnnotationKind::AutocompleteContains | AnnotationKind::AutocompleteExcludes
-        )
-    }) {
+    for annotation in annotations.filter(|a| a.kind.is_ide()) {
+        // The query and the labels that must be found for it, in order.
+        if annotation.kind == AnnotationKind::SymbolSearch {
+            let strings = parse_strings(&annotation.text);
+            let Some((query, labels)) = strings.split_first() else { continue };
+            let found: Vec<_> = typst_ide::symbol_search(world, query)
+                .into_iter()
+                .map(|c| c.label.to_string())
+                .collect();
+            let positions: Vec<_> = labels
+                .iter()
+                .map(|label| found.iter().position(|c| c == label))
+                .collect();
+            if positions.contains(&None) || !positions.windows(2).all(|w| w[0] < w[1]) {
+                let found = &found[..found.len().min(10)];
+                report_ide(output, source, line, i, annotation, &found);
+                *ok = false;
+            }
+            continue;
+        }
+
         // Ok cause we checked in parsing that range was Some for this annotation
-        let cursor = annotation.range.as_ref().unwrap().start;
+        let range = annotation.range.clone().unwrap();
+        let cursor = range.start;
+
+        // The hovered range and the signature, or else the value, must match,
+        // with the cursor at the end of the range.
+        if annotation.kind == AnnotationKind::Hover {
+            let found = typst_ide::hover(world, source, range.end).map(|hover| {
+                (hover.range, hover.signature.unwrap_or(hover.repr).to_string())
+            });
+            let expected = parse_strings(&annotation.text);
+            if !found.as_ref().is_some_and(|(found, shown)| {
+                *found == range
+                    && expected.iter().all(|text| shown.contains(text.as_str()))
+            }) {
+                report_ide(output, source, line, i, annotation, &found);
+                *ok = false;
+            }
+            continue;
+        }
+
+        // The function's name and the active parameter, if any.
+        if annotation.kind == AnnotationKind::Signature {
+            let found: Vec<String> = typst_ide::signature_help(world, source, cursor)
+                .map(|help| {
+                    let name = help.label.split('(').next().unwrap_or_default();
+                    let name = name.to_string();
+                    let active = help.active.map(|i| help.params[i].label.to_string());
+                    std::iter::once(name).chain(active).collect()
+                })
+                .unwrap_or_default();
+            if found != parse_strings(&annotation.text) {
+                report_ide(output, source, line, i, annotation, &found);
+                *ok = false;
+            }
+            continue;
+        }
+
+        // The file of the definition and its line, with the defining range in
+        // brackets. No strings mean that there is no definition.
+        if annotation.kind == AnnotationKind::Definition {
+            let found: Vec<String> = typst_ide::definition(world, source, cursor)
+                .map(|(file, start, end)| {
+                    let name = file.id().vpath().as_rootless_path().file_name();
+                    let name = name.unwrap_or_default().to_string_lossy().into_owned();
+                    let line = file.byte_to_line(start).unwrap();
+                    let range = file.line_to_range(line).unwrap();
+                    let text = file.text();
+                    let marked = format!(
+                        "{}[{}]{}",
+                        &text[range.start..start],
+                        &text[start..end],
+                        text[end..range.end].trim_end(),
+                    );
+                    vec![name, marked]
+                })
+                .unwrap_or_default();
+            if found != parse_strings(&annotation.text) {
+                report_ide(output, source, line, i, annotation, &found);
+                *ok = false;
+            }
+            continue;
+        }
 
         // todo, use document if is_some to test labels autocomplete
-        let completions = typst_ide::autocomplete(world, None, source, cursor, true)
-            .map(|(_, c)| c)
-            .unwrap_or_default()
+        let (from, to, completions, _) =
+            typst_ide::autocomplete(world, None, source, cursor, true, None, None)
+                .unwrap_or((cursor, cursor, vec![], false));
+
+        if annotation.kind == AnnotationKind::AutocompleteApply {
+            let strings = parse_strings(&annotation.text);
+            let [label, expected] = strings.as_slice() else { continue };
+            let applied = completions
+                .iter()
+                .find(|c| c.label == label.as_str())
+                .map(|c| apply_completion(source, cursor, from, to, c));
+            if applied.as_ref() != Some(expected) {
+                report_ide(output, source, line, i, annotation, &applied);
+                *ok = false;
+            }
+            continue;
+        }
+
+        // The completion's detail must contain the given text.
+        if annotation.kind == AnnotationKind::AutocompleteDetail {
+            let strings = parse_strings(&annotation.text);
+            let [label, expected] = strings.as_slice() else { continue };
+            let detail = completions
+                .iter()
+                .find(|c| c.label == label.as_str())
+                .map(|c| c.detail.clone().unwrap_or_default());
+            if !detail
+                .as_ref()
+                .is_some_and(|detail| detail.contains(expected.as_str()))
+            {
+                report_ide(output, source, line, i, annotation, &detail);
+                *ok = false;
+            }
+            continue;
+        }
+
+        // The completions must all be there, in the given order.
+        if annotation.kind == AnnotationKind::AutocompleteOrder {
+            let positions: Vec<_> = parse_strings(&annotation.text)
+                .iter()
+                .map(|label| completions.iter().position(|c| c.label == label.as_str()))
+                .collect();
+            if positions.contains(&None) || !positions.windows(2).all(|w| w[0] < w[1]) {
+                let found: Vec<_> = completions.iter().map(|c| &c.label).collect();
+                report_ide(output, source, line, i, annotation, &found);
+                *ok = false;
+            }
+            continue;
+        }
+
+        let completions = completions
             .into_iter()
             .map(|c| c.label.to_string())
             .collect::<HashSet<_>>();
-        let completions =
-            completions.iter().map(|s| s.as_str()).collect::<HashSet<&str>>();
 
         let must_contain_or_exclude = parse_string_list(&annotation.text);
         let missing =
@@ -784,6 +929,49 @@ fn test_autocomplete<'a>(
     }
 }
 
+/// Report an IDE annotation that doesn't hold, along with what was found
+/// instead.
+fn report_ide(
+    output: &mut String,
+    source: &Source,
+    line: usize,
+    i: usize,
+    annotation: &Annotation,
+    found: &dyn std::fmt::Debug,
+) {
+    writeln!(output, "  Subtest {i} does not match expected {}.", annotation.kind)
+        .unwrap();
+    write!(output, "  for annotation | ").unwrap();
+    print_annotation(output, source, line, annotation);
+    writeln!(output, "    Found | {found:?}").unwrap();
+}
+
+/// Apply a completion and its additional edits to the source, returning the
+/// edited line of the cursor.
+fn apply_completion(
+    source: &Source,
+    cursor: usize,
+    from: usize,
+    to: usize,
+    completion: &typst_ide::Completion,
+) -> String {
+    let apply = completion.apply.clone().unwrap_or_else(|| completion.label.clone());
+    let mut edits = completion.additional_edits.clone();
+    edits.push((from, to, apply));
+    edits.sort_by_key(|&(start, _, _)| std::cmp::Reverse(start));
+
+    let mut text = source.text().to_string();
+    let mut start = source.line_to_byte(source.byte_to_line(cursor).unwrap()).unwrap();
+    for (edit_start, edit_end, replacement) in edits {
+        text.replace_range(edit_start..edit_end, &replacement);
+        if edit_start < start {
+            start = start + replacement.len() - (edit_end - edit_start);
+        }
+    }
+
+    text[start..].lines().next().unwrap_or_default().into()
+}
+
 #[allow(clippy::too_many_arguments)]
 fn test_diagnostics<'a>(
     output: &mut String,
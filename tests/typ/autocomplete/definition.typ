@@ -0,0 +1,29 @@
+// Autocomplete: true
+// Ref: false
+
+---
+// Let bindings, also destructuring ones.
+// Definition: 3:5 "definition.typ", "#let [width] = 1cm"
+// Definition: 4:3 "definition.typ", "#let (a, [b]) = (1, 2)"
+#let width = 1cm
+#let (a, b) = (1, 2)
+#width
+#b
+
+---
+// Loop patterns and closure parameters.
+// Definition: 1:36 "definition.typ", "#for (key, [value]) in (a: 1) { value }"
+// Definition: 2:23 "definition.typ", "#let double([x]) = 2 * x"
+#for (key, value) in (a: 1) { value }
+#let double(x) = 2 * x
+
+---
+// Imported names are followed into their module.
+// Definition: 2:7 "theme.typ", "#let [accent] = blue"
+#import "modules/theme.typ": accent
+#accent
+
+---
+// Names from the standard library have no definition.
+// Definition: 1:5
+#rgb(0, 0, 0)
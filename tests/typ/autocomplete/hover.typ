@@ -0,0 +1,20 @@
+// Autocomplete: true
+// Ref: false
+
+---
+// Global functions show their signature.
+// Hover: 1:2-1:5 "rgb(", "alpha"
+// Hover: 2:13-2:16 "rgb("
+#rgb(0, 0, 0)
+#text(fill: rgb(0, 0, 0))[]
+
+---
+// Local bindings show their value.
+// Hover: 2:2-2:7 "(1, 2)"
+#let items = (1, 2)
+#items
+
+---
+// Fields are resolved, so symbols are previewed.
+// Hover: 1:8-1:12 "😀"
+#emoji.face
@@ -0,0 +1,105 @@
+// Autocomplete: true
+// Ref: false
+
+---
+// Footnotes offer references and citations.
+// Autocomplete contains: 1:11 "reference", "citation"
+// Autocomplete excludes: 1:11 "]"
+#footnote[]
+
+---
+// An unclosed content block can be closed.
+// Autocomplete contains: 1:6 "]"
+// Autocomplete contains: 1:11 "]", "strong text"
+#box[hello
+
+---
+// Headings with and without a label, and deeper ones.
+// Autocomplete contains: 1:6 "heading", "heading (labelled)", "heading (level 2)"
+Hello
+
+---
+// Pages are set up at the start of the document.
+// Autocomplete contains: 2:1 "page setup"
+#set text(size: 12pt)
+
+
+---
+// But not after content.
+// Autocomplete excludes: 2:1 "page setup"
+Hello
+
+
+---
+// Embedded code in markup nested within code.
+// Autocomplete contains: 1:5 "text", "let binding"
+// Autocomplete excludes: 1:5 "red", "none", "page setup"
+#{[#]}
+
+---
+// Autocomplete contains: 1:8 "text", "table"
+// Autocomplete excludes: 1:8 "red"
+#{ [#te] }
+
+---
+// Lists are styled alongside their structure.
+// Autocomplete contains: 2:2 "list text style"
+#set list(indent: 1em)
+#
+
+---
+// Blank lines and comments may come in between.
+// Autocomplete contains: 4:2 "list text style"
+#set list(indent: 1em)
+
+// Now for the styling.
+#
+
+---
+// Autocomplete excludes: 2:2 "list text style"
+#set enum(indent: 1em)
+#
+
+---
+// Captions offer references and citations.
+// Autocomplete contains: 1:32 "reference", "citation", "italic text"
+#figure([], caption: [See also ])
+
+---
+// Autocomplete excludes: 1:6 "italic text", "citation"
+#box[]
+
+---
+// References to labels anywhere in the source.
+// Autocomplete contains: 1:6 "intro", "tiger", "euler"
+See @
+= Introduction <intro>
+#figure(image("tiger.jpg")) <tiger>
+$ e^(i pi) = -1 $ <euler>
+
+---
+// Citation keys from the bibliography file.
+// Autocomplete contains: 1:8 "netwok", "arrgh", "quark"
+#cite(<)
+#bibliography("/files/works.bib")
+
+---
+// Completing within an identifier.
+// Autocomplete contains: 1:4 "page", "par"
+#page
+
+---
+// Raw text and comments aren't Typst.
+// Autocomplete excludes: 1:11 "strong text", "Rust"
+// Autocomplete excludes: 2:17 "strong text"
+```rust fn main```
+Text // a comment
+
+---
+// Raw block languages, also in code.
+// Autocomplete contains: 1:9 "Rust"
+// Autocomplete contains: 2:7 "Rust", "Python"
+// Autocomplete contains: 3:6 "Python"
+#{ ```ru x``` }
+#{ ``` x``` }
+```py
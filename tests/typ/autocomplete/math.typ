@@ -0,0 +1,57 @@
+// Autocomplete: true
+// Ref: false
+
+---
+// Autocomplete contains: 1:3 "vec"
+// Autocomplete contains: 1:7 "mat"
+// Autocomplete contains: 1:11 "cases"
+$v + m + c
+
+---
+// Accents are called with their base.
+// Autocomplete contains: -1 "hat", "hat accent", "arrow accent"
+$h
+
+---
+// Symbol variants by name.
+// Autocomplete contains: 1:5 "arrow", "arrow.r", "arrow.l"
+// Autocomplete excludes: 1:5 "plus.circle"
+// Autocomplete contains: 2:8 "arrow.l.double"
+$arr$
+$arrldo$
+
+---
+// Short fragments only find variants starting like them.
+// Autocomplete contains: -1 "arrow.r", "arrow.l"
+// Autocomplete excludes: -1 "harpoon.rt", "plus.circle.arrow"
+$ar
+
+---
+// Autocomplete contains: -1 "sqrt", "root"
+$r
+
+---
+// Autocomplete contains: -1 "cancel", "accent"
+$c
+
+---
+// Bounds of an integral.
+// Autocomplete contains: 2:12 "n", "oo", "index range"
+// Autocomplete contains: 2:13 "n", "oo"
+// Autocomplete excludes: 2:13 "index range"
+#let n = 5
+$ integral_^ $
+
+---
+// Local bindings rank first in attachments, even when they shadow a function.
+// Autocomplete order: 2:12 "vec", "oo"
+#let vec = 5
+$ integral_^ $
+
+---
+// Shorthands, but not behind a name.
+// Autocomplete contains: 1:3 "->", "=>", ">="
+// Autocomplete detail: 1:3 "->", "Shorthand for →"
+// Autocomplete excludes: 2:3 "->", "=>"
+$ $
+$a$
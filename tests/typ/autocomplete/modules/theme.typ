@@ -0,0 +1,5 @@
+// A theme module to import in autocompletion tests.
+// Ref: false
+
+#let callout = rect
+#let accent = blue
@@ -0,0 +1,329 @@
+// Autocomplete: true
+// Ref: false
+
+---
+// A parameter that is either a toggle or a specific length.
+// Autocomplete contains: -1 "true", "false", "relative length"
+// Autocomplete detail: 1:17 "false", "Disabled."
+// Autocomplete detail: 1:17 "true", "Enabled, with the default length."
+// Autocomplete detail: 1:17 "relative length", "Enabled, with a specific relative length."
+#outline(indent:
+
+---
+// Parameters set by an earlier set rule for the same target come last.
+// Autocomplete contains: 2:11 "size", "font", "fill"
+// Autocomplete order: 2:11 "fill", "font", "size"
+#set text(size: 12pt)
+#set text()
+
+---
+// The default value comes first.
+// Autocomplete contains: -1 "auto", "true", "false"
+// Autocomplete order: -1 "auto", "false", "true"
+#set text(hyphenate:
+
+---
+// Autocomplete contains: -1 "-0.2em", "0.2em"
+#set text(baseline:
+
+---
+// Autocomplete contains: -1 "true", "false"
+// Autocomplete detail: 1:16 "true", "Yes / Enabled. Whether to display the full numbering"
+// Autocomplete detail: 1:16 "false", "No / Disabled. Whether to display the full numbering"
+#set enum(full:
+
+---
+// Autocomplete contains: 1:22 "left", "center", "auto"
+#table(align: (left, ))
+
+---
+// Autocomplete contains: 1:17 "auto", "fraction"
+// Autocomplete excludes: 1:17 "array"
+#grid(columns: ())
+
+---
+// Autocomplete contains: -1 "full-page fill", "full-page image"
+#set page(background:
+
+---
+// Curated values for outline styling.
+// Autocomplete contains: 1:16 "repeat[.]", "custom leader"
+// Autocomplete detail: 1:16 "repeat[.]", "A dotted leader."
+#outline(fill: )
+
+---
+// Syntax definition files, relative to the current file.
+// Autocomplete contains: 1:17 "../../files/SExpressions.sublime-syntax"
+// Autocomplete excludes: 1:17 "../../files/halcyon.tmTheme", "../../files/hello.txt"
+#raw(syntaxes: ())
+
+---
+// Autocomplete contains: 1:20 "../../files/SExpressions.sublime-syntax"
+// Autocomplete apply: 1:20 "../../files/SExpressions.sublime-syntax", "#set raw(syntaxes: \"../../files/SExpressions.sublime-syntax\")"
+#set raw(syntaxes: )
+
+---
+// The alternative description of an image.
+// Autocomplete contains: 1:21 "alt", "fit", "width"
+#image("tiger.jpg", )
+
+---
+// Parameters of table cells.
+// Autocomplete contains: 1:13 "colspan", "fill", "align", "inset"
+#table.cell()
+
+---
+// Curated spans of grid cells.
+// Autocomplete contains: 1:21 "2", "3"
+#grid.cell(colspan: )
+
+---
+// Colors and alignments for table cells.
+// Autocomplete contains: 1:18 "red", "blue"
+// Autocomplete contains: 2:19 "center", "left"
+#table.cell(fill: )
+#table.cell(align: )
+
+---
+// The alpha component of an RGB color.
+// Autocomplete contains: 1:18 "255"
+// Autocomplete detail: 1:18 "255", "Fully opaque."
+// Autocomplete excludes: 2:14 "255"
+#rgb(10, 20, 30, )
+#rgb(10, 20, )
+
+---
+// Font families with and without a pre-typed quote.
+// Autocomplete contains: 1:13 "\"Linux Libertine\"", "\"New Computer Modern\""
+// Autocomplete contains: 2:14 "\"Linux Libertine\"", "\"New Computer Modern\""
+// Autocomplete apply: 1:13 "\"Linux Libertine\"", "#text(font: \"Linux Libertine\")"
+// Autocomplete apply: 2:14 "\"Linux Libertine\"", "#text(font: \"Linux Libertine\")"
+#text(font: )
+#text(font: "")
+
+---
+// Preset page margins.
+// Autocomplete contains: 1:15 "1in", "2.5cm"
+// Autocomplete excludes: 1:15 "72pt", "70.87pt"
+// Autocomplete detail: 1:15 "1in", "A narrow margin. (1in — 72pt — 2.54cm)"
+// Autocomplete apply: 1:15 "2.5cm", "#page(margin: 2.5cm)"
+#page(margin: )
+
+---
+// Parameters and alignments for enum numbers.
+// Autocomplete contains: 1:11 "number-align", "full", "numbering"
+// Autocomplete contains: 2:25 "end + top", "start + top", "start + bottom"
+#set enum()
+#set enum(number-align: )
+
+---
+// Fractional spacing.
+// Autocomplete contains: 1:4 "1fr", "2fr", "fraction"
+#h()
+
+---
+// Autocomplete contains: 1:12 "block", "quotes", "attribution"
+#set quote()
+
+---
+// Autocomplete contains: -1 "true", "false"
+// Autocomplete detail: 1:18 "true", "Sets the quote apart as an indented block."
+// Autocomplete detail: 1:18 "false", "Runs the quote inline with the surrounding text."
+#set quote(block:
+
+---
+// Conflicting parameters are still offered.
+// Autocomplete contains: 1:24 "width", "height", "margin"
+#set page(paper: "a5", )
+
+---
+// Bullet glyphs for list markers.
+// Autocomplete contains: -1 "[•]", "[▪]", "[▸]", "[–]", "[—]"
+// Autocomplete detail: 1:18 "[•]", "sym.bullet"
+// Autocomplete apply: 1:18 "marker array", "#set list(marker: ([${1:•}], [${2:◦}], [${3:▪}])"
+#set list(marker:
+
+---
+// Numbering patterns whose counting symbols differ per nesting level.
+// Autocomplete contains: -1 "\"1.a.i.\"", "\"I.A.1.\""
+// Autocomplete detail: -1 "\"1.a.i.\"", "The counting symbols apply to nested enums."
+#set enum(numbering:
+
+---
+// Glyphs for term separators.
+// Autocomplete contains: -1 "[:]", "[–]", "[—]", "[→]"
+#set terms(separator:
+
+---
+// Units behind a bare number.
+// Autocomplete contains: 1:19 "pt", "em", "cm"
+// Autocomplete excludes: 1:19 "%", "deg", "fr"
+#set text(size: 12)
+
+---
+// Autocomplete contains: 1:16 "pt", "%"
+// Autocomplete excludes: 1:16 "deg"
+#box(width: 2.5)
+
+---
+// Autocomplete excludes: 1:26 "pt", "em"
+#set heading(numbering: 1)
+
+---
+// Autocomplete contains: 1:7 "pt", "deg", "fr"
+#{ 3.5 }
+
+---
+// Keys of dictionaries.
+// Autocomplete contains: 1:16 "top", "paint", "thickness"
+#rect(stroke: ())
+
+---
+// Autocomplete contains: 1:26 "bottom", "paint"
+// Autocomplete excludes: 1:26 "top", "red"
+#rect(stroke: (top: red, ))
+
+---
+// Autocomplete contains: 1:17 "top", "x", "rest"
+#rect(inset: (to))
+
+---
+// Autocomplete contains: 1:31 "bottom", "inside"
+// Autocomplete excludes: 1:31 "top"
+#set page(margin: (top: 1cm, b))
+
+---
+// Arrays don't have keys.
+// Autocomplete contains: 1:21 "blue"
+// Autocomplete excludes: 1:21 "paint"
+#rect(stroke: (red, ))
+
+---
+// Predefined colors.
+// Autocomplete contains: 1:13 "navy", "maroon", "silver", "rgb()"
+// Autocomplete contains: 2:15 "navy"
+#rect(fill: )
+#rect(fill: nv)
+
+---
+// Values for the next positional parameter only.
+// Autocomplete contains: 1:8 "top", "bottom"
+// Autocomplete excludes: 2:13 "top", "bottom"
+#place()
+#place(top, )
+
+---
+// Editing the name of a named argument.
+// Autocomplete contains: 1:9 "weight"
+// Autocomplete contains: 2:9 "weight"
+// Autocomplete contains: 3:11 "fill"
+#text(we: bold)
+#text(we:)
+#text(fill: red)[Hi]
+
+---
+// Concrete string choices without a generic placeholder.
+// Autocomplete contains: -1 "\"vancouver\"", "\"chicago-fullnotes\""
+// Autocomplete excludes: -1 "string"
+#set bibliography(style:
+
+---
+// Paper sizes.
+// Autocomplete contains: 1:18 "\"a4\"", "\"us-letter\""
+#set page(paper: )
+
+---
+// Language and region codes.
+// Autocomplete contains: 1:17 "\"en\"", "\"de\"", "\"zh\""
+// Autocomplete contains: 2:19 "\"CH\"", "\"TW\""
+// Autocomplete detail: 2:19 "\"PT\"", "Portugal"
+// Autocomplete excludes: 2:19 "\"en\""
+#set text(lang: )
+#set text(region: )
+
+---
+// OpenType feature tags.
+// Autocomplete contains: 1:18 "smcp", "onum"
+// Autocomplete contains: 2:26 "onum"
+// Autocomplete excludes: 2:26 "smcp"
+#text(features: ())[]
+#text(features: ("smcp", ))[]
+
+---
+// Font weights by name and by number, and styles within a string.
+// Autocomplete contains: 1:15 "\"thin\"", "\"bold\"", "\"black\"", "700"
+// Autocomplete excludes: 1:15 "integer"
+// Autocomplete contains: 2:15 "\"italic\"", "\"oblique\""
+// Autocomplete apply: 3:17 "\"bold\"", "#text(weight: \"bold\")[]"
+#text(weight: )[]
+#text(style: "")[]
+#text(weight: "b")[]
+
+---
+// Within an array argument, elements are completed instead of parameters.
+// Autocomplete contains: 1:18 "auto", "1fr"
+// Autocomplete excludes: 1:18 "rows", "gutter"
+// Autocomplete excludes: 2:27 "rows", "gutter"
+#grid(columns: ( ))
+#grid(columns: (1fr, 2fr) )
+
+---
+// Space behind an unclosed array is still within it.
+// Autocomplete contains: -1 "auto", "1fr"
+// Autocomplete excludes: -1 "rows", "gutter"
+#grid(columns: (1fr, 
+
+---
+// Elements of a collection as a positional argument.
+// Autocomplete contains: 1:19 "red", "rgb()"
+// Autocomplete excludes: 1:19 "key: value"
+#gradient.linear((), blue)
+
+---
+// Collections that aren't arguments.
+// Autocomplete contains: 1:4 "key: value", "rgb"
+// Autocomplete excludes: 2:7 "key: value"
+// Autocomplete contains: 2:7 "rgb"
+#{()}
+#{(1, )}
+
+---
+// The parameter isn't offered within its own value.
+// Autocomplete excludes: 1:13 "fill", "stroke"
+// Autocomplete excludes: 2:14 "fill", "stroke"
+// Autocomplete contains: 2:14 "red"
+#rect(fill: )
+#rect(fill: r)
+
+---
+// Short defaults of parameters.
+// Autocomplete detail: 1:7 "size", "(default: 11pt)"
+// Autocomplete detail: 1:7 "fill", "(default: luma(0%))"
+#text()
+
+---
+// Numeral styling values explain which font feature they select.
+// Autocomplete detail: 1:23 "\"lining\"", "capital text"
+// Autocomplete detail: 1:23 "\"old-style\"", "upper- and lowercase text"
+// Autocomplete detail: 2:24 "\"tabular\"", "Numbers of equal width"
+#set text(number-type: )
+#set text(number-width: )
+
+---
+// Footnotes can be numbered with symbols.
+// Autocomplete contains: -1 "\"*\""
+// Autocomplete detail: -1 "\"*\"", "Marks footnotes with symbols"
+#set footnote(numbering:
+
+---
+// Text parameters are labeled with their category.
+// Autocomplete detail: 1:11 "ligatures", "[features]"
+// Autocomplete detail: 1:11 "fill", "[color]"
+// Autocomplete detail: 1:11 "lang", "[language]"
+#set text()
+
+---
+// Parameters set by an earlier set rule say so.
+// Autocomplete detail: 2:11 "size", "Already set above."
+#set text(size: 12pt)
+#set text()
@@ -0,0 +1,79 @@
+// Autocomplete: true
+// Ref: false
+
+---
+// Autocomplete contains: -1 "equation"
+// Autocomplete excludes: -1 "vec", "sqrt"
+#set math.e
+
+---
+// Autocomplete contains: 2:6 "text", "par", "page", "heading", "block", "math"
+// Autocomplete order: 2:6 "text", "par", "page", "heading", "align"
+// Autocomplete order: 2:6 "heading", "block"
+// Autocomplete excludes: 2:6 "x", "lorem"
+#let x = 1
+#set
+
+---
+// Settable elements of the math module.
+// Autocomplete contains: 1:11 "equation", "mat", "vec", "cases"
+// Autocomplete excludes: 1:11 "sqrt", "alpha"
+#set math.
+
+---
+// Parameters of a settable math element.
+// Autocomplete contains: 1:15 "delim", "gap"
+#set math.vec()
+
+---
+// Recipes for an element selector.
+// Autocomplete contains: 1:16 "replacement", "replacement (string)", "transformation"
+// Autocomplete order: 1:16 "replacement", "replacement (string)"
+// Autocomplete apply: 2:16 "replacement", "#show heading: [${1:content}]"
+#show heading: it
+#show heading: 
+
+---
+// Recipes for a text selector.
+// Autocomplete contains: 1:20 "replacement", "replacement (string)"
+// Autocomplete order: 1:20 "replacement (string)", "replacement"
+// Autocomplete apply: 2:20 "replacement (string)", "#show regex(\"\\d\"): \"${1:text}\""
+#show regex("\d"): it
+#show regex("\d"): 
+
+---
+// Settable elements of a module imported under a new name.
+// Autocomplete contains: 2:10 "callout"
+// Autocomplete excludes: 2:10 "accent"
+#import "modules/theme.typ" as thm
+#set thm.
+
+---
+// Parameters of a settable element from a renamed module.
+// Autocomplete contains: 2:18 "fill", "radius"
+#import "modules/theme.typ" as thm
+#set thm.callout()
+
+---
+// Recipes for a filtered element selector.
+// Autocomplete contains: 1:32 "transformation"
+#show heading.where(level: 1): it
+
+---
+// Selectors for elements with certain fields.
+// Autocomplete contains: 1:7 "heading", "heading.where", "figure.where"
+// Autocomplete excludes: 1:7 "metadata.where"
+#show : it
+
+---
+// Set rules in show rules.
+// Autocomplete contains: 1:25 "fill", "size"
+// Autocomplete contains: 2:20 "text", "par"
+// Autocomplete contains: 3:23 "text"
+#show heading: set text()
+#show heading: set text
+#show heading: set tex
+
+---
+// Autocomplete contains: -1 "fill", "size"
+#show heading: set text(
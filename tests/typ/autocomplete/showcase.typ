@@ -6,8 +6,218 @@
 // Autocomplete excludes: -1 "foo"
 #i
 
+---
+// Labels starting with the typed text come first, equally ranked ones by
+// label.
+// Autocomplete order: -1 "page", "pagebreak", "par", "parbreak", "place"
+#pa
+
 ---
 
 // Autocomplete contains: -1 "insert", "remove", "len", "all"
 // Autocomplete excludes: -1 "foobar", "foo",
 #().
+
+---
+// Autocomplete contains: 2:6 "item", "push", "value"
+#import "../compiler/module.typ" as mod
+#mod.
+
+---
+// Autocomplete contains: 2:4 "mod"
+#import "../compiler/module.typ" as mod
+#mo
+
+---
+// Autocomplete contains: -1 "measure", "layout"
+// Autocomplete excludes: -1 "measured content", "size-dependent content"
+#
+
+---
+// Callbacks called during layout can depend on sizes.
+// Autocomplete contains: 1:9 "size-dependent content", "measured content"
+// Autocomplete apply: 1:9 "size-dependent content", "#layout(size => ${1})"
+// Autocomplete excludes: 2:11 "size-dependent content", "measured content"
+#layout()
+#style()
+
+---
+// Autocomplete contains: 2:2 "page setup"
+#let x = 1
+#
+
+---
+// Items of imported modules are imported along with their completion.
+// Autocomplete contains: 2:5 "callout"
+#import "modules/theme.typ": accent
+#cal
+
+---
+// Autocomplete contains: 2:5 "callout"
+#import "modules/theme.typ"
+#cal
+
+---
+// The import of an item extends the existing import statement.
+// Autocomplete apply: 1:34 "callout", "#import \"modules/theme.typ\": callout; #callout(${})"
+#import "modules/theme.typ"; #cal
+
+---
+// Autocomplete apply: 1:42 "callout", "#import \"modules/theme.typ\": accent, callout; #callout(${})"
+#import "modules/theme.typ": accent; #cal
+
+---
+// Local bindings shadow global ones.
+// Autocomplete contains: 2:5 "rect"
+#let rect(body) = box(stroke: red, body)
+#rec
+
+---
+// Closure parameters in the closure's body.
+// Autocomplete contains: 1:34 "x", "y", "rest"
+#{ let f = (x, y, ..rest) => x + }
+
+---
+// Autocomplete excludes: 1:40 "first", "rest"
+#{ let f = (first, ..rest) => first; f() }
+
+---
+// Autocomplete contains: 1:59 "func", "fields", "location", "level", "body"
+// Autocomplete excludes: 1:59 "children"
+// Autocomplete contains: 2:59 "level", "outlined"
+#locate(loc => { let hs = query(heading, loc); hs.first(). })
+#locate(loc => query(heading.where(level: 1), loc).last(). )
+
+---
+// Queries are found through aliases, but not when shadowed.
+// Autocomplete contains: 2:53 "level"
+// Autocomplete excludes: 4:57 "level"
+#let q = query
+#locate(loc => { let hs = q(heading, loc); hs.at(0). })
+#let query(..args) = (1, 2)
+#locate(loc => { let hs = query(heading, loc); hs.at(0). })
+
+---
+// The result of a call that isn't evaluated.
+// Autocomplete contains: -1 "lighten", "darken"
+#let paler(c) = rgb(c).
+
+---
+// Autocomplete contains: -1 "lighten", "darken"
+#let paler(c) = rgb(c).light
+
+---
+// Methods on the results of method calls.
+// Autocomplete contains: -1 "sorted", "push", "join"
+#let f(y) = (1, 2, 3).map(x => x + y).rev().
+
+---
+// The result of `first` depends on the array.
+// Autocomplete excludes: -1 "sorted", "push", "join"
+#let f(y) = (1, 2, 3).map(x => x + y).first().
+
+---
+// Fields of all types a variable may have.
+// Autocomplete contains: 1:30 "len", "split", "push"
+#for x in ("a", (1, 2)) { x.len() }
+
+---
+// Names are matched by subsequence.
+// Autocomplete contains: -1 "table"
+// Autocomplete excludes: -1 "text", "rect"
+#tbl
+
+---
+// Files and directories relative to the current file.
+// Autocomplete contains: 1:10 "modules/", "markup.typ"
+// Autocomplete excludes: 1:10 "showcase.typ", "theme.typ"
+#import ""
+
+---
+// Autocomplete contains: 1:18 "theme.typ"
+// Autocomplete excludes: 1:18 "modules/"
+#import "modules/"
+
+---
+// Paths whose string isn't closed yet.
+// Autocomplete contains: 1:14 "modules/"
+// Autocomplete apply: 1:14 "modules/", "#import \"modules/"
+#import "modu
+
+---
+// Autocomplete apply: 1:21 "theme.typ", "#include \"modules/theme.typ\""
+#include "modules/th
+
+---
+// Autocomplete contains: 1:23 "module.typ", "modules/"
+// Autocomplete excludes: 1:23 "autocomplete/"
+#include "../compiler/"
+
+---
+// Names bound by destructuring, including renamed keys.
+// Autocomplete contains: 2:3 "width", "h", "rest"
+// Autocomplete excludes: 2:3 "height"
+#let (width, height: h, ..rest) = (width: 1pt, height: 2pt, depth: 3pt)
+#{}
+
+---
+// Keys of a destructured dictionary.
+// Autocomplete contains: 2:7 "width", "height"
+// Autocomplete contains: 3:14 "height"
+// Autocomplete excludes: 3:14 "width", "size"
+#let size = (width: 1pt, height: 2pt)
+#let () = size
+#let (width, ) = size
+
+---
+// Fields of the element that a show rule transforms.
+// Autocomplete contains: 1:25 "level", "body", "outlined", "func"
+// Autocomplete contains: 2:43 "level", "numbering"
+#show heading: it => it.
+#show heading.where(level: 1): it => it.nu
+
+---
+// Content is wrapped by postfix completions.
+// Autocomplete apply: 2:4 "strong", "#strong(x)"
+// Autocomplete apply: 3:27 "emph", "#show heading: it => emph(it)"
+#let x = [Hello]
+#x.
+#show heading: it => it.em
+
+---
+// Modifiers of a symbol.
+// Autocomplete contains: -1 "r", "l", "double"
+// Autocomplete excludes: -1 "arrow"
+#sym.arrow.
+
+---
+// Escape sequences behind a backslash.
+// Autocomplete contains: 1:7 "\\n", "\\\"", "\\u{...}"
+#{ "a\" }
+
+---
+// Without a backslash, a string argument still gets the argument's completions.
+// Autocomplete contains: 1:8 "encoding", "string"
+// Autocomplete excludes: 1:8 "\\n", "\\u{...}"
+#read("")
+
+---
+// Backslashes in raw text are literal.
+// Autocomplete excludes: 1:9 "\\n", "\\u{...}"
+#raw(`a\`.text)
+
+---
+// Nothing more to import behind a wildcard.
+// Autocomplete excludes: 1:32 "callout", "accent", "heading"
+// Autocomplete excludes: -1 "callout", "accent", "heading"
+#import "modules/theme.typ": *, 
+
+---
+// Exports of a package.
+// Autocomplete contains: 1:30 "add", "*"
+#import "@test/adder:0.1.0": 
+
+---
+// Packages that are unavailable have no exports.
+// Autocomplete excludes: 1:32 "add", "*"
+#import "@test/missing:0.1.0": 
@@ -0,0 +1,31 @@
+// Autocomplete: true
+// Ref: false
+
+---
+// Named arguments, also before their value is written.
+// Signature: 1:15 "text", "size:"
+// Signature: 2:25 "text", "fill:"
+#text(size: 12pt)[]
+#text(size: 12pt, fill: )[]
+
+---
+// Positional arguments are counted.
+// Signature: 1:14 "rgb", "blue"
+#rgb(10, 20, )
+
+---
+// A variadic parameter takes all remaining arguments.
+// Signature: 1:17 "list", "..children"
+#list([a], [b], )
+
+---
+// In nested calls, the innermost one wins.
+// Signature: 1:20 "rgb", "green"
+// Signature: 2:24 "text", "body"
+#text(fill: rgb(1, ))[]
+#text(fill: rgb(1, 2), )
+
+---
+// Outside of an argument list.
+// Signature: 1:4
+#text
@@ -0,0 +1,18 @@
+// Autocomplete: true
+// Ref: false
+
+---
+// Exact matches come before prefix matches, which come before others.
+// Symbol search: "arrow.r", "arrow.r", "arrow.r.long", "sym.arrow.r"
+// Symbol search: "alpha", "alpha", "sym.alpha"
+
+---
+// Symbols are found by their glyph and their shorthands.
+// Symbol search: "α", "alpha"
+// Symbol search: "->", "arrow.r"
+// Symbol search: "!=", "eq.not"
+
+---
+// Symbols are found by their Unicode name.
+// Symbol search: "rightwards arrow", "arrow.r"
+// Symbol search: "greek small letter alpha", "alpha"